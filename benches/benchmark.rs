@@ -1,6 +1,10 @@
 use {
-    aoc_2021::run_with,
-    criterion::{criterion_group, criterion_main, Criterion},
+    aoc_2021::{
+        largest_pairwise_magnitude_parallel, run_with, solve_with_config, AlignmentStrategy,
+        ScannerSolverConfig,
+    },
+    criterion::{criterion_group, criterion_main, BenchmarkId, Criterion},
+    std::{fs::read_to_string, thread::available_parallelism},
 };
 
 fn criterion_benchmark(c: &mut Criterion) {
@@ -13,5 +17,57 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn day_18_pairwise_benchmark(c: &mut Criterion) {
+    let input = read_to_string("input/day18.txt").unwrap();
+    let lines = input.trim().lines().collect::<Vec<_>>();
+    let mut thread_counts = vec![
+        1,
+        2,
+        4,
+        available_parallelism().map_or(1, |threads| threads.get()),
+    ];
+    thread_counts.sort_unstable();
+    thread_counts.dedup();
+    let mut group = c.benchmark_group("day_18_pairwise");
+    [25, 50, 100].into_iter().for_each(|size| {
+        let input = lines[..size.min(lines.len())].join("\n");
+        thread_counts.iter().for_each(|threads| {
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}_threads", threads), size),
+                &input,
+                |b, input| b.iter(|| largest_pairwise_magnitude_parallel(input, *threads)),
+            );
+        });
+    });
+    group.finish();
+}
+
+fn day_19_alignment_benchmark(c: &mut Criterion) {
+    let input = read_to_string("input/day19.txt").unwrap();
+    let mut group = c.benchmark_group("day_19_alignment");
+    [
+        AlignmentStrategy::BruteForce,
+        AlignmentStrategy::Fingerprint,
+    ]
+    .into_iter()
+    .for_each(|alignment_strategy| {
+        let config = ScannerSolverConfig {
+            alignment_strategy,
+            ..Default::default()
+        };
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{:?}", alignment_strategy)),
+            &config,
+            |b, config| b.iter(|| solve_with_config(&input, config)),
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    day_18_pairwise_benchmark,
+    day_19_alignment_benchmark
+);
 criterion_main!(benches);
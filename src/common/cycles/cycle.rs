@@ -0,0 +1,81 @@
+/// A cycle found in a sequence of states, where the state after start steps is the first one
+/// that comes up again, and it repeats every period steps from then on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cycle<T> {
+    pub start: usize,
+    pub period: usize,
+    pub state: T,
+}
+
+impl<T> Cycle<T> {
+    /// Gets the smallest number of steps which ends up in the same state as running the given
+    /// number of steps, which is never more than start + period - 1.
+    pub fn equivalent_steps(&self, steps: usize) -> usize {
+        if steps < self.start {
+            steps
+        } else {
+            self.start + (steps - self.start) % self.period
+        }
+    }
+
+    /// Adds up some value for each of the given number of steps, such as a count of the
+    /// changes made by each step, without running them all. The values are the ones for
+    /// each step up to the end of the first period (start + period of them), where the
+    /// first value is for the first step. Panics if there are fewer values than that.
+    pub fn extrapolate_sum(&self, values: &[u64], steps: usize) -> u128 {
+        let sum = |values: &[u64]| values.iter().map(|value| *value as u128).sum::<u128>();
+        let cycle_length = self.start + self.period;
+        assert!(
+            values.len() >= cycle_length,
+            "Expected at least {} values but found {}.",
+            cycle_length,
+            values.len()
+        );
+        if steps <= cycle_length {
+            return sum(&values[..steps]);
+        }
+        let (cycles, remainder) = (
+            (steps - self.start) / self.period,
+            (steps - self.start) % self.period,
+        );
+        sum(&values[..self.start])
+            + sum(&values[self.start..cycle_length]) * cycles as u128
+            + sum(&values[self.start..self.start + remainder])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equivalent_steps() {
+        let cycle = Cycle {
+            start: 3,
+            period: 4,
+            state: (),
+        };
+        assert_eq!(cycle.equivalent_steps(2), 2);
+        assert_eq!(cycle.equivalent_steps(3), 3);
+        assert_eq!(cycle.equivalent_steps(7), 3);
+        assert_eq!(cycle.equivalent_steps(1_000_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_extrapolate_sum() {
+        let cycle = Cycle {
+            start: 2,
+            period: 3,
+            state: (),
+        };
+        let values = [10, 20, 1, 2, 3];
+        assert_eq!(cycle.extrapolate_sum(&values, 0), 0);
+        assert_eq!(cycle.extrapolate_sum(&values, 4), 33);
+        assert_eq!(cycle.extrapolate_sum(&values, 5), 36);
+        assert_eq!(cycle.extrapolate_sum(&values, 10), 30 + 6 + 6 + 1 + 2);
+        assert_eq!(
+            cycle.extrapolate_sum(&values, 3_000_000_002),
+            30 + 6 * 1_000_000_000
+        );
+    }
+}
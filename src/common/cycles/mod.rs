@@ -0,0 +1,112 @@
+mod cycle;
+
+pub use cycle::Cycle;
+
+/// Finds the cycle that a sequence of states ends up in, starting from the initial state and
+/// running the step function to get each next state. States are compared by the keys they
+/// give, so anything which doesn't affect later steps (such as running totals) can be left
+/// out. This is Brent's algorithm, which only keeps two states around so works for states too
+/// large to remember every one of. Gives None if no state has repeated within the maximum
+/// number of steps, which stops it from running forever on sequences which never repeat.
+pub fn find_cycle<T: Clone, K: PartialEq>(
+    initial: &T,
+    mut step: impl FnMut(&mut T),
+    key: impl Fn(&T) -> K,
+    max_steps: usize,
+) -> Option<Cycle<T>> {
+    // Find the period by moving the hare along and comparing it to the tortoise, which
+    // jumps to the hare each time the number of steps between them reaches a power of two.
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise_key = key(initial);
+    let mut hare = initial.clone();
+    step(&mut hare);
+    let mut steps = 1;
+    while key(&hare) != tortoise_key {
+        if steps >= max_steps {
+            return None;
+        }
+        if power == period {
+            tortoise_key = key(&hare);
+            power *= 2;
+            period = 0;
+        }
+        step(&mut hare);
+        period += 1;
+        steps += 1;
+    }
+
+    // Find the start by moving both along together, with the hare a period ahead.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    (0..period).for_each(|_| step(&mut hare));
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+    Some(Cycle {
+        start,
+        period,
+        state: tortoise,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 -> ...
+        let step = |state: &mut u32| *state = if *state == 6 { 3 } else { *state + 1 };
+        assert_eq!(
+            find_cycle(&0, step, |state| *state, 100),
+            Some(Cycle {
+                start: 3,
+                period: 4,
+                state: 3
+            })
+        );
+        assert_eq!(
+            find_cycle(&5, step, |state| *state, 100),
+            Some(Cycle {
+                start: 0,
+                period: 4,
+                state: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_cycle_fixed_point() {
+        let step = |state: &mut u32| *state = (*state + 1).min(10);
+        assert_eq!(
+            find_cycle(&0, step, |state| *state, 100),
+            Some(Cycle {
+                start: 10,
+                period: 1,
+                state: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_cycle_by_key() {
+        // The count keeps going up but doesn't affect the value, so it's left out of the key.
+        let step = |(value, count): &mut (u32, u32)| {
+            *value = (*value * 3) % 7;
+            *count += 1;
+        };
+        let cycle = find_cycle(&(1, 0), step, |(value, _)| *value, 100).unwrap();
+        assert_eq!((cycle.start, cycle.period, cycle.state), (0, 6, (1, 0)));
+    }
+
+    #[test]
+    fn test_find_cycle_never_repeats() {
+        assert_eq!(
+            find_cycle(&0u64, |state| *state += 1, |state| *state, 1000),
+            None
+        );
+    }
+}
@@ -0,0 +1,4 @@
+mod parse_graph_error;
+mod undirected_graph;
+
+pub use {parse_graph_error::ParseGraphError, undirected_graph::UndirectedGraph};
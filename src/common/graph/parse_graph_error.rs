@@ -0,0 +1,27 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Error cases for parsing a graph from lines of "a-b" edges, where line is the (1 based)
+/// number of the line that the error was found on.
+#[derive(Debug, PartialEq)]
+pub enum ParseGraphError {
+    MissingSeparator { line: usize },
+    EmptyName { line: usize },
+}
+
+impl Display for ParseGraphError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::MissingSeparator { line } => {
+                write!(f, "ParseGraphError::MissingSeparator {{ line: {} }}", line)
+            }
+            Self::EmptyName { line } => {
+                write!(f, "ParseGraphError::EmptyName {{ line: {} }}", line)
+            }
+        }
+    }
+}
+
+impl Error for ParseGraphError {}
@@ -0,0 +1,346 @@
+use {
+    super::ParseGraphError,
+    std::{
+        borrow::Borrow,
+        collections::{HashMap, VecDeque},
+        io::{Result, Write},
+        str::FromStr,
+    },
+};
+
+/// An undirected graph where each vertex has a name. Names are interned when they're first
+/// added, giving the vertex an id which is its index into the names and adjacency lists, so
+/// everything else works with ids. Repeated edges are only stored once.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UndirectedGraph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl UndirectedGraph {
+    /// Builds a graph from lines in the form "a-b" which each add an edge between the two
+    /// named vertices. Empty lines are skipped.
+    pub fn from_links(
+        iter: impl Iterator<Item = impl Borrow<str>>,
+    ) -> std::result::Result<Self, ParseGraphError> {
+        let mut graph = Self::default();
+        iter.enumerate().try_for_each(|(idx, link)| {
+            let (line, link) = (idx + 1, link.borrow().trim());
+            if link.is_empty() {
+                return Ok(());
+            }
+            let (a, b) = link
+                .split_once('-')
+                .ok_or(ParseGraphError::MissingSeparator { line })?;
+            let (a, b) = (a.trim(), b.trim());
+            if a.is_empty() || b.is_empty() {
+                return Err(ParseGraphError::EmptyName { line });
+            }
+            let (a, b) = (graph.add_vertex(a), graph.add_vertex(b));
+            graph.add_edge(a, b);
+            Ok(())
+        })?;
+        Ok(graph)
+    }
+
+    /// Gets the id of the vertex with the given name, adding it if it hasn't been seen before.
+    pub fn add_vertex(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.adjacency.push(vec![]);
+        id
+    }
+
+    /// Adds an edge between the two vertices, unless they're already joined.
+    pub fn add_edge(&mut self, a: usize, b: usize) {
+        if !self.adjacency[a].contains(&b) {
+            self.adjacency[a].push(b);
+            if a != b {
+                self.adjacency[b].push(a);
+            }
+        }
+    }
+
+    /// Gets the number of vertices.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Checks whether there are no vertices at all.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Gets the id of the vertex with the given name, if there is one.
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    /// Gets the name of the vertex with the given id.
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// Gets the ids of the vertices joined to the vertex, in the order the edges were added.
+    pub fn neighbours(&self, id: usize) -> &[usize] {
+        &self.adjacency[id]
+    }
+
+    /// Gets the number of edges joined to the vertex.
+    pub fn degree(&self, id: usize) -> usize {
+        self.adjacency[id].len()
+    }
+
+    /// Iterates over every edge once, as the pair of vertex ids with the smaller id first.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(a, neighbours)| {
+                neighbours
+                    .iter()
+                    .filter(move |b| a <= **b)
+                    .map(move |b| (a, *b))
+            })
+    }
+
+    /// Groups the vertices into sets which are connected to each other. Each component is
+    /// sorted and the components are ordered by their smallest id.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.len()];
+        (0..self.len())
+            .filter_map(|root| {
+                if seen[root] {
+                    return None;
+                }
+                seen[root] = true;
+                let mut component = vec![];
+                let mut queue = VecDeque::from([root]);
+                while let Some(id) = queue.pop_front() {
+                    component.push(id);
+                    self.adjacency[id].iter().for_each(|next| {
+                        if !seen[*next] {
+                            seen[*next] = true;
+                            queue.push_back(*next);
+                        }
+                    });
+                }
+                component.sort_unstable();
+                Some(component)
+            })
+            .collect()
+    }
+
+    /// Finds the edges whose removal would split a component into two, with the smaller id
+    /// first in each pair and the pairs sorted. This is Tarjan's algorithm, using a stack
+    /// rather than recursion so large graphs can't overflow the function stack.
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        let mut discovered = vec![None; self.len()];
+        let mut low = vec![0; self.len()];
+        let mut time = 0;
+        let mut bridges = vec![];
+        (0..self.len()).for_each(|root| {
+            if discovered[root].is_some() {
+                return;
+            }
+            discovered[root] = Some(time);
+            low[root] = time;
+            time += 1;
+            // Each entry is a vertex, the vertex it was reached from and how many of its
+            // neighbours have been looked at so far.
+            let mut stack = vec![(root, None, 0)];
+            while let Some((id, parent, next)) = stack.last_mut() {
+                let (id, parent) = (*id, *parent);
+                if let Some(neighbour) = self.adjacency[id].get(*next).copied() {
+                    *next += 1;
+                    if Some(neighbour) == parent {
+                        continue;
+                    }
+                    match discovered[neighbour] {
+                        Some(time) => low[id] = low[id].min(time),
+                        None => {
+                            discovered[neighbour] = Some(time);
+                            low[neighbour] = time;
+                            time += 1;
+                            stack.push((neighbour, Some(id), 0));
+                        }
+                    }
+                } else {
+                    stack.pop();
+                    if let Some(parent) = parent {
+                        low[parent] = low[parent].min(low[id]);
+                        if Some(low[id]) > discovered[parent] {
+                            bridges.push((parent.min(id), parent.max(id)));
+                        }
+                    }
+                }
+            }
+        });
+        bridges.sort_unstable();
+        bridges
+    }
+
+    /// Writes the graph in the Graphviz DOT language. The attributes function is given each
+    /// vertex id and can give attributes for it such as "shape=box", or an empty string.
+    pub fn write_dot(
+        &self,
+        writer: &mut impl Write,
+        attributes: impl Fn(usize) -> String,
+    ) -> Result<()> {
+        writeln!(writer, "graph {{")?;
+        (0..self.len()).try_for_each(|id| {
+            let attributes = attributes(id);
+            if attributes.is_empty() {
+                writeln!(writer, "    {};", quote(self.name(id)))
+            } else {
+                writeln!(writer, "    {} [{}];", quote(self.name(id)), attributes)
+            }
+        })?;
+        self.edges().try_for_each(|(a, b)| {
+            writeln!(
+                writer,
+                "    {} -- {};",
+                quote(self.name(a)),
+                quote(self.name(b))
+            )
+        })?;
+        writeln!(writer, "}}")
+    }
+
+    /// Writes each vertex on its own line, followed by a colon and the names of its
+    /// neighbours separated by spaces.
+    pub fn write_adjacency_list(&self, writer: &mut impl Write) -> Result<()> {
+        (0..self.len()).try_for_each(|id| {
+            write!(writer, "{}:", self.name(id))?;
+            self.adjacency[id]
+                .iter()
+                .try_for_each(|next| write!(writer, " {}", self.name(*next)))?;
+            writeln!(writer)
+        })
+    }
+}
+
+impl FromStr for UndirectedGraph {
+    type Err = ParseGraphError;
+
+    fn from_str(string: &str) -> std::result::Result<Self, Self::Err> {
+        Self::from_links(string.lines())
+    }
+}
+
+// Quotes the name as a DOT identifier, escaping any quotes inside it.
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two triangles joined by a path through c-d-e, with a separate pair and a lone vertex.
+    const INPUT: &str = "
+        a-b
+        b-c
+        c-a
+        c-d
+        d-e
+        e-f
+        f-g
+        g-e
+        b-a
+
+        x-y
+        z-z";
+
+    fn names(graph: &UndirectedGraph, ids: &[usize]) -> Vec<String> {
+        ids.iter().map(|id| graph.name(*id).to_string()).collect()
+    }
+
+    #[test]
+    fn test_from_links() {
+        let graph = INPUT.parse::<UndirectedGraph>().unwrap();
+        assert_eq!(graph.len(), 10);
+        let a = graph.id("a").unwrap();
+        assert_eq!(names(&graph, graph.neighbours(a)), vec!["b", "c"]);
+        assert_eq!(graph.degree(graph.id("c").unwrap()), 3);
+        assert_eq!(graph.degree(graph.id("z").unwrap()), 1);
+        assert_eq!(graph.edges().count(), 10);
+        assert_eq!(graph.id("w"), None);
+    }
+
+    #[test]
+    fn test_from_links_errors() {
+        assert_eq!(
+            "a-b\n\nab".parse::<UndirectedGraph>(),
+            Err(ParseGraphError::MissingSeparator { line: 3 })
+        );
+        assert_eq!(
+            "a-".parse::<UndirectedGraph>(),
+            Err(ParseGraphError::EmptyName { line: 1 })
+        );
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let graph = INPUT.parse::<UndirectedGraph>().unwrap();
+        let components = graph
+            .connected_components()
+            .iter()
+            .map(|component| names(&graph, component))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            components,
+            vec![
+                vec!["a", "b", "c", "d", "e", "f", "g"],
+                vec!["x", "y"],
+                vec!["z"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bridges() {
+        let graph = INPUT.parse::<UndirectedGraph>().unwrap();
+        let bridges = graph
+            .bridges()
+            .into_iter()
+            .map(|(a, b)| (graph.name(a), graph.name(b)))
+            .collect::<Vec<_>>();
+        assert_eq!(bridges, vec![("c", "d"), ("d", "e"), ("x", "y")]);
+    }
+
+    #[test]
+    fn test_write_dot() {
+        let graph = "a-b\nb-\"c\"".parse::<UndirectedGraph>().unwrap();
+        let mut output = vec![];
+        graph
+            .write_dot(&mut output, |id| {
+                if id == 0 {
+                    "shape=box".to_string()
+                } else {
+                    String::new()
+                }
+            })
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "graph {\n    \"a\" [shape=box];\n    \"b\";\n    \"\\\"c\\\"\";\n    \"a\" -- \"b\";\n    \"b\" -- \"\\\"c\\\"\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_write_adjacency_list() {
+        let graph = "a-b\nb-c\nc-a".parse::<UndirectedGraph>().unwrap();
+        let mut output = vec![];
+        graph.write_adjacency_list(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "a: b c\nb: a c\nc: b a\n"
+        );
+    }
+}
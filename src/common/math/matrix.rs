@@ -1,6 +1,11 @@
-use std::{
-    iter::Sum,
-    ops::{Add, Mul},
+use {
+    super::Vector,
+    crate::common::num::{ExactDiv, ZeroOne},
+    std::{
+        array::from_fn,
+        iter::Sum,
+        ops::{Add, Index, IndexMut, Mul, Neg, Sub},
+    },
 };
 
 /// Represents a mathematical matrix along with operations to manipulate
@@ -13,6 +18,210 @@ impl<T, const ROWS: usize, const COLS: usize> Matrix<T, ROWS, COLS> {
     pub const fn new(array: [[T; COLS]; ROWS]) -> Self {
         Self(array)
     }
+
+    /// Gets the elements of the matrix as an array of rows.
+    pub fn rows(&self) -> &[[T; COLS]; ROWS] {
+        &self.0
+    }
+
+    /// Consumes the matrix, giving back the array of rows.
+    pub fn into_rows(self) -> [[T; COLS]; ROWS] {
+        self.0
+    }
+
+    /// Produces a new matrix by applying the function to every element.
+    pub fn map<U>(self, mut func: impl FnMut(T) -> U) -> Matrix<U, ROWS, COLS> {
+        Matrix(self.0.map(|row| row.map(&mut func)))
+    }
+
+    // Divides every element by the divisor, or gives None if any of them don't divide exactly.
+    fn exact_div(self, divisor: T) -> Option<Self>
+    where
+        T: Copy + ExactDiv,
+    {
+        let quotients = self.map(|value| value.exact_div(divisor));
+        quotients
+            .0
+            .iter()
+            .flatten()
+            .all(Option::is_some)
+            .then(|| quotients.map(Option::unwrap))
+    }
+
+    /// Converts every element into another type.
+    pub fn convert<U: From<T>>(self) -> Matrix<U, ROWS, COLS> {
+        self.map(U::from)
+    }
+
+    /// Converts every element into another type, failing with the first element which
+    /// doesn't fit into the new type.
+    pub fn try_convert<U: TryFrom<T>>(self) -> Result<Matrix<U, ROWS, COLS>, U::Error> {
+        let converted = self
+            .0
+            .into_iter()
+            .flatten()
+            .map(U::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut elements = converted.into_iter();
+        Ok(Matrix(from_fn(|_| {
+            from_fn(|_| elements.next().expect("matrix has ROWS * COLS elements"))
+        })))
+    }
+}
+
+impl<T: Copy, const ROWS: usize, const COLS: usize> Matrix<T, ROWS, COLS> {
+    /// Swaps the rows and columns of the matrix.
+    pub fn transpose(&self) -> Matrix<T, COLS, ROWS> {
+        Matrix(from_fn(|row| from_fn(|col| self.0[col][row])))
+    }
+
+    /// Multiplies every element by the given scalar.
+    pub fn scale(&self, scalar: T) -> Self
+    where
+        T: Mul<Output = T>,
+    {
+        Matrix(from_fn(|row| from_fn(|col| self.0[row][col] * scalar)))
+    }
+
+    /// Multiplies the matrix element-wise with another of the same size (the Hadamard product).
+    pub fn component_mul(&self, rhs: &Self) -> Self
+    where
+        T: Mul<Output = T>,
+    {
+        Matrix(from_fn(|row| {
+            from_fn(|col| self.0[row][col] * rhs.0[row][col])
+        }))
+    }
+}
+
+impl<T: Copy + ZeroOne, const SIZE: usize> Matrix<T, SIZE, SIZE> {
+    /// Constructs the identity matrix, with ones along the diagonal and zeros elsewhere.
+    pub const fn identity() -> Self {
+        let mut array = [[T::ZERO; SIZE]; SIZE];
+        let mut idx = 0;
+        while idx < SIZE {
+            array[idx][idx] = T::ONE;
+            idx += 1;
+        }
+        Self(array)
+    }
+}
+
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Matrix<T, 2, 2> {
+    /// Calculates the determinant of the matrix.
+    pub fn determinant(&self) -> T {
+        self.0[0][0] * self.0[1][1] - self.0[0][1] * self.0[1][0]
+    }
+
+    /// Calculates the inverse of the matrix, or None if it's singular. The adjugate is
+    /// divided through by the determinant, so for integer types this is also None unless
+    /// every entry divides exactly.
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: ExactDiv + Neg<Output = T>,
+    {
+        let [[a, b], [c, d]] = self.0;
+        Matrix([[d, -b], [-c, a]]).exact_div(self.determinant())
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T>> Matrix<T, 3, 3> {
+    /// Calculates the determinant of the matrix by expanding along the first row.
+    pub fn determinant(&self) -> T {
+        let cofactors = self.cofactors();
+        self.0[0][0] * cofactors.0[0][0]
+            + self.0[0][1] * cofactors.0[0][1]
+            + self.0[0][2] * cofactors.0[0][2]
+    }
+
+    /// Calculates the inverse of the matrix, or None if it's singular. The adjugate is
+    /// divided through by the determinant, so for integer types this is also None unless
+    /// every entry divides exactly.
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: ExactDiv,
+    {
+        self.cofactors().transpose().exact_div(self.determinant())
+    }
+
+    // Calculates the matrix of cofactors. Taking the rows and columns cyclically means the
+    // alternating signs of the cofactors fall out of the order of the products.
+    fn cofactors(&self) -> Self {
+        let m = &self.0;
+        Matrix(from_fn(|row| {
+            from_fn(|col| {
+                let (r1, r2) = ((row + 1) % 3, (row + 2) % 3);
+                let (c1, c2) = ((col + 1) % 3, (col + 2) % 3);
+                m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1]
+            })
+        }))
+    }
+}
+
+impl<T, const ROWS: usize, const COLS: usize> Index<(usize, usize)> for Matrix<T, ROWS, COLS> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.0[row][col]
+    }
+}
+
+impl<T, const ROWS: usize, const COLS: usize> IndexMut<(usize, usize)> for Matrix<T, ROWS, COLS> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.0[row][col]
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const ROWS: usize, const COLS: usize> Add<&Matrix<T, ROWS, COLS>>
+    for &Matrix<T, ROWS, COLS>
+{
+    type Output = Matrix<T, ROWS, COLS>;
+
+    fn add(self, rhs: &Matrix<T, ROWS, COLS>) -> Self::Output {
+        Matrix(from_fn(|row| {
+            from_fn(|col| self.0[row][col] + rhs.0[row][col])
+        }))
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const ROWS: usize, const COLS: usize> Add
+    for Matrix<T, ROWS, COLS>
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const ROWS: usize, const COLS: usize> Sub<&Matrix<T, ROWS, COLS>>
+    for &Matrix<T, ROWS, COLS>
+{
+    type Output = Matrix<T, ROWS, COLS>;
+
+    fn sub(self, rhs: &Matrix<T, ROWS, COLS>) -> Self::Output {
+        Matrix(from_fn(|row| {
+            from_fn(|col| self.0[row][col] - rhs.0[row][col])
+        }))
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const ROWS: usize, const COLS: usize> Sub
+    for Matrix<T, ROWS, COLS>
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl<T: Neg<Output = T>, const ROWS: usize, const COLS: usize> Neg for Matrix<T, ROWS, COLS> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(T::neg)
+    }
 }
 
 impl<
@@ -56,28 +265,30 @@ impl<
     }
 }
 
-impl<T: Add<T, Output = T> + Clone + Mul<T, Output = T>> Mul<&(T, T, T)> for &Matrix<T, 3, 3> {
-    type Output = (T, T, T);
+impl<
+        T: Add<Output = T> + Copy + Mul<Output = T> + ZeroOne,
+        const ROWS: usize,
+        const COLS: usize,
+    > Mul<&Vector<T, COLS>> for &Matrix<T, ROWS, COLS>
+{
+    type Output = Vector<T, ROWS>;
 
-    fn mul(self, rhs: &(T, T, T)) -> Self::Output {
-        (
-            (self.0[0][0].clone() * rhs.0.clone())
-                + (self.0[0][1].clone() * rhs.1.clone())
-                + (self.0[0][2].clone() * rhs.2.clone()),
-            (self.0[1][0].clone() * rhs.0.clone())
-                + (self.0[1][1].clone() * rhs.1.clone())
-                + (self.0[1][2].clone() * rhs.2.clone()),
-            (self.0[2][0].clone() * rhs.0.clone())
-                + (self.0[2][1].clone() * rhs.1.clone())
-                + (self.0[2][2].clone() * rhs.2.clone()),
-        )
+    fn mul(self, rhs: &Vector<T, COLS>) -> Self::Output {
+        Vector::new(from_fn(|row| {
+            (0..COLS).fold(T::ZERO, |total, col| total + self.0[row][col] * rhs[col])
+        }))
     }
 }
 
-impl<T: Add<T, Output = T> + Clone + Mul<T, Output = T>> Mul<(T, T, T)> for Matrix<T, 3, 3> {
-    type Output = (T, T, T);
+impl<
+        T: Add<Output = T> + Copy + Mul<Output = T> + ZeroOne,
+        const ROWS: usize,
+        const COLS: usize,
+    > Mul<Vector<T, COLS>> for Matrix<T, ROWS, COLS>
+{
+    type Output = Vector<T, ROWS>;
 
-    fn mul(self, rhs: (T, T, T)) -> Self::Output {
+    fn mul(self, rhs: Vector<T, COLS>) -> Self::Output {
         &self * &rhs
     }
 }
@@ -139,6 +350,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_matrix_identity() {
+        assert_eq!(
+            Matrix::<i64, 3, 3>::identity(),
+            Matrix::new([[1, 0, 0], [0, 1, 0], [0, 0, 1]])
+        );
+        assert_eq!(Matrix::<f64, 1, 1>::identity(), Matrix::new([[1.0]]));
+        let matrix = Matrix::new([[8, 9], [5, -1]]);
+        assert_eq!(&matrix * &Matrix::identity(), matrix);
+    }
+
+    #[test]
+    fn test_matrix_transpose() {
+        assert_eq!(
+            Matrix::new([[0, -1, 2], [4, 11, 2]]).transpose(),
+            Matrix::new([[0, 4], [-1, 11], [2, 2]])
+        );
+    }
+
+    #[test]
+    fn test_matrix_indexing() {
+        let mut matrix = Matrix::new([[0, -1, 2], [4, 11, 2]]);
+        assert_eq!(matrix[(0, 1)], -1);
+        assert_eq!(matrix[(1, 0)], 4);
+        matrix[(1, 2)] = 7;
+        assert_eq!(matrix.rows(), &[[0, -1, 2], [4, 11, 7]]);
+        assert_eq!(matrix.into_rows(), [[0, -1, 2], [4, 11, 7]]);
+    }
+
+    #[test]
+    fn test_matrix_determinant() {
+        assert_eq!(Matrix::new([[8, 9], [5, -1]]).determinant(), -53);
+        assert_eq!(
+            Matrix::new([[2, 3, 1], [1, 2, 1], [1, 1, 1]]).determinant(),
+            1
+        );
+        assert_eq!(
+            Matrix::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]).determinant(),
+            0
+        );
+        assert_eq!(
+            Matrix::new([[0, 0, -1], [1, 0, 0], [0, -1, 0]]).determinant(),
+            1
+        );
+        assert_eq!(
+            Matrix::new([[-1, 0, 0], [0, 1, 0], [0, 0, 1]]).determinant(),
+            -1
+        );
+    }
+
+    #[test]
+    fn test_matrix_inverse() {
+        assert_eq!(
+            Matrix::new([[4.0, 7.0], [2.0, 6.0]]).inverse(),
+            Some(Matrix::new([[0.6, -0.7], [-0.2, 0.4]]))
+        );
+        assert_eq!(Matrix::new([[1, 2], [2, 4]]).inverse(), None);
+        assert_eq!(Matrix::new([[2, 0], [0, 1]]).inverse(), None);
+        assert_eq!(
+            Matrix::new([[2.0, 0.0], [0.0, 1.0]]).inverse(),
+            Some(Matrix::new([[0.5, 0.0], [0.0, 1.0]]))
+        );
+        let matrix = Matrix::new([[2, 3, 1], [1, 2, 1], [1, 1, 1]]);
+        let inverse = matrix.inverse().unwrap();
+        assert_eq!(inverse, Matrix::new([[1, -2, 1], [0, 1, -1], [-1, 1, 1]]));
+        assert_eq!(&matrix * &inverse, Matrix::identity());
+        let rotation = Matrix::new([[0, 0, -1], [1, 0, 0], [0, -1, 0]]);
+        assert_eq!(rotation.inverse(), Some(rotation.transpose()));
+        assert_eq!(
+            Matrix::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]).inverse(),
+            None
+        );
+        assert_eq!(
+            Matrix::new([[1, 1, 0], [0, 1, 1], [1, 0, 1]]).inverse(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_matrix_element_wise() {
+        let a = Matrix::new([[1, 2], [3, 4]]);
+        let b = Matrix::new([[5, -6], [7, 0]]);
+        assert_eq!(&a + &b, Matrix::new([[6, -4], [10, 4]]));
+        assert_eq!(&a - &b, Matrix::new([[-4, 8], [-4, 4]]));
+        assert_eq!(a.component_mul(&b), Matrix::new([[5, -12], [21, 0]]));
+        assert_eq!(a.scale(3), Matrix::new([[3, 6], [9, 12]]));
+        assert_eq!(-a.clone(), Matrix::new([[-1, -2], [-3, -4]]));
+        assert_eq!(a.clone() + b.clone() - b, a);
+    }
+
+    #[test]
+    fn test_matrix_conversion() {
+        let matrix = Matrix::new([[1u8, 2], [3, 255]]);
+        assert_eq!(
+            matrix.clone().convert::<i64>(),
+            Matrix::new([[1i64, 2], [3, 255]])
+        );
+        assert_eq!(
+            Matrix::new([[1i64, -2], [3, 4]]).try_convert::<u8>().ok(),
+            None
+        );
+        assert_eq!(
+            Matrix::new([[1i64, 2], [3, 255]]).try_convert::<u8>(),
+            Ok(matrix)
+        );
+    }
+
     #[test]
     fn test_point_rotation() {
         const ROT_90_AROUND_Z_COUNTER_CLOCKWISE: Matrix<i64, 3, 3> =
@@ -147,8 +465,29 @@ mod tests {
             Matrix::new([[0, 1, 0], [-1, 0, 0], [0, 0, 1]]);
         const ROT_90_AROUND_Y_COUNTER_CLOCKWISE: Matrix<i64, 3, 3> =
             Matrix::new([[0, 0, -1], [1, 0, 0], [0, -1, 0]]);
-        assert_eq!(ROT_90_AROUND_Z_COUNTER_CLOCKWISE * (0, 10, 0), (-10, 0, 0));
-        assert_eq!(ROT_270_AROUND_Z_COUNTER_CLOCKWISE * (0, 10, 0), (10, 0, 0));
-        assert_eq!(ROT_90_AROUND_Y_COUNTER_CLOCKWISE * (10, 0, 0), (0, 10, 0));
+        assert_eq!(
+            ROT_90_AROUND_Z_COUNTER_CLOCKWISE * Vector::new([0, 10, 0]),
+            Vector::new([-10, 0, 0])
+        );
+        assert_eq!(
+            ROT_270_AROUND_Z_COUNTER_CLOCKWISE * Vector::new([0, 10, 0]),
+            Vector::new([10, 0, 0])
+        );
+        assert_eq!(
+            &ROT_90_AROUND_Y_COUNTER_CLOCKWISE * &Vector::new([10, 0, 0]),
+            Vector::new([0, 10, 0])
+        );
+    }
+
+    #[test]
+    fn test_matrix_vector_multiplication() {
+        assert_eq!(
+            Matrix::new([[0, -1, 2], [4, 11, 2]]) * Vector::new([3, 1, 6]),
+            Vector::new([11, 35])
+        );
+        assert_eq!(
+            Matrix::new([[8, 9], [5, -1]]) * Vector::new([-2, 4]),
+            Vector::new([20, -14])
+        );
     }
 }
@@ -1,3 +1,9 @@
 mod matrix;
+mod parse_vector_error;
+mod rotations;
+mod vector;
 
 pub use matrix::Matrix;
+pub use parse_vector_error::ParseVectorError;
+pub use rotations::{proper_rotations, signed_permutations};
+pub use vector::Vector;
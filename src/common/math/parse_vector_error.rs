@@ -0,0 +1,27 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result},
+};
+
+/// Error cases for parsing a vector from its comma separated elements, where E is the
+/// error from parsing a single element.
+#[derive(Debug, PartialEq)]
+pub enum ParseVectorError<E> {
+    ParseElementError(E),
+    NotEnoughParts,
+    TooManyParts,
+}
+
+impl<E: Display> Display for ParseVectorError<E> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::ParseElementError(error) => {
+                write!(f, "ParseVectorError::ParseElementError({})", error)
+            }
+            Self::NotEnoughParts => write!(f, "ParseVectorError::NotEnoughParts"),
+            Self::TooManyParts => write!(f, "ParseVectorError::TooManyParts"),
+        }
+    }
+}
+
+impl<E: Debug + Display> Error for ParseVectorError<E> {}
@@ -0,0 +1,152 @@
+use {super::Matrix, crate::common::num::ZeroOne, std::ops::Neg};
+
+/// Generates the rotations of an N dimensional grid onto itself, i.e. every way of lining the
+/// axes up with each other (and pointing them either way) which keeps the handedness the same.
+/// In 2D these are the 4 quarter turns and in 3D the 24 ways a cube can be facing. The
+/// identity is always placed first.
+pub fn proper_rotations<T: Copy + Neg<Output = T> + ZeroOne, const N: usize>(
+) -> Vec<Matrix<T, N, N>> {
+    generate(false).0
+}
+
+/// Generates every signed permutation matrix of size N, which is the proper rotations along
+/// with their mirror images. The proper rotations are all placed first (in the same order
+/// that they're given by proper_rotations), followed by the reflections.
+pub fn signed_permutations<T: Copy + Neg<Output = T> + ZeroOne, const N: usize>(
+) -> Vec<Matrix<T, N, N>> {
+    let (mut proper, reflections) = generate(true);
+    proper.extend(reflections);
+    proper
+}
+
+// Builds a matrix for every permutation of the axes and every combination of their signs,
+// splitting them by whether they're a rotation or a reflection. Rather than calculating the
+// determinant, which is only implemented for small sizes, the sign of each matrix comes from
+// the parity of the permutation multiplied by every flipped axis.
+fn generate<T: Copy + Neg<Output = T> + ZeroOne, const N: usize>(
+    include_reflections: bool,
+) -> (Vec<Matrix<T, N, N>>, Vec<Matrix<T, N, N>>) {
+    let mut proper = vec![];
+    let mut reflections = vec![];
+    permutations(N).into_iter().for_each(|permutation| {
+        let inversions = (0..N)
+            .flat_map(|i| (i + 1..N).map(move |j| (i, j)))
+            .filter(|(i, j)| permutation[*i] > permutation[*j])
+            .count();
+        (0..1u32 << N).for_each(|signs| {
+            let is_proper = (inversions + signs.count_ones() as usize).is_multiple_of(2);
+            if is_proper || include_reflections {
+                let mut matrix = Matrix::new([[T::ZERO; N]; N]);
+                permutation.iter().enumerate().for_each(|(row, col)| {
+                    matrix[(row, *col)] = if signs & (1 << row) == 0 {
+                        T::ONE
+                    } else {
+                        -T::ONE
+                    };
+                });
+                if is_proper {
+                    proper.push(matrix);
+                } else {
+                    reflections.push(matrix);
+                }
+            }
+        });
+    });
+    (proper, reflections)
+}
+
+// Generates the permutations of 0..size in lexicographic order, so the identity is first.
+fn permutations(size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+    (0..size)
+        .flat_map(|first| {
+            permutations(size - 1).into_iter().map(move |rest| {
+                std::iter::once(first)
+                    .chain(
+                        rest.into_iter()
+                            .map(|idx| if idx >= first { idx + 1 } else { idx }),
+                    )
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::HashSet};
+
+    // Checks that the product of any two of the matrices is also one of them.
+    fn is_closed<const N: usize>(matrices: &[Matrix<i64, N, N>]) -> bool {
+        let set = matrices.iter().collect::<HashSet<_>>();
+        matrices
+            .iter()
+            .all(|a| matrices.iter().all(|b| set.contains(&(a * b))))
+    }
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(
+            permutations(3),
+            vec![
+                vec![0, 1, 2],
+                vec![0, 2, 1],
+                vec![1, 0, 2],
+                vec![1, 2, 0],
+                vec![2, 0, 1],
+                vec![2, 1, 0]
+            ]
+        );
+        assert_eq!(permutations(0), vec![Vec::<usize>::new()]);
+    }
+
+    #[test]
+    fn test_proper_rotations_2d() {
+        let rotations = proper_rotations::<i64, 2>();
+        assert_eq!(rotations.iter().collect::<HashSet<_>>().len(), 4);
+        assert_eq!(rotations[0], Matrix::identity());
+        assert!(rotations.contains(&Matrix::new([[0, -1], [1, 0]])));
+        assert!(rotations.iter().all(|rotation| rotation.determinant() == 1));
+        assert!(is_closed(&rotations));
+    }
+
+    #[test]
+    fn test_proper_rotations_3d() {
+        let rotations = proper_rotations::<i64, 3>();
+        assert_eq!(rotations.iter().collect::<HashSet<_>>().len(), 24);
+        assert_eq!(rotations[0], Matrix::identity());
+        assert!(rotations.iter().all(|rotation| rotation.determinant() == 1
+            && rotation.inverse() == Some(rotation.transpose())));
+        assert!(is_closed(&rotations));
+    }
+
+    #[test]
+    fn test_signed_permutations_2d() {
+        let matrices = signed_permutations::<i64, 2>();
+        assert_eq!(matrices.iter().collect::<HashSet<_>>().len(), 8);
+        assert_eq!(matrices[..4], proper_rotations::<i64, 2>()[..]);
+        assert!(matrices[4..]
+            .iter()
+            .all(|matrix| matrix.determinant() == -1));
+        assert!(is_closed(&matrices));
+    }
+
+    #[test]
+    fn test_signed_permutations_3d() {
+        let matrices = signed_permutations::<i64, 3>();
+        assert_eq!(matrices.iter().collect::<HashSet<_>>().len(), 48);
+        assert_eq!(matrices[..24], proper_rotations::<i64, 3>()[..]);
+        assert!(matrices[24..]
+            .iter()
+            .all(|matrix| matrix.determinant() == -1));
+        assert!(is_closed(&matrices));
+    }
+
+    #[test]
+    fn test_other_element_types() {
+        assert_eq!(proper_rotations::<f64, 2>()[0], Matrix::identity());
+        assert_eq!(signed_permutations::<i32, 4>().len(), 384);
+    }
+}
@@ -0,0 +1,370 @@
+use {
+    super::parse_vector_error::ParseVectorError,
+    crate::common::num::{ToF64, ZeroOne},
+    std::{
+        array::from_fn,
+        ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+        str::FromStr,
+    },
+};
+
+/// Represents a mathematical vector with a fixed number of dimensions, which is mostly used
+/// to hold points and the offsets between them.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vector<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> Vector<T, N> {
+    /// Constructs a new Vector, taking ownership of the given array.
+    pub const fn new(array: [T; N]) -> Self {
+        Self(array)
+    }
+
+    /// Gets the elements of the vector as an array.
+    pub fn as_array(&self) -> &[T; N] {
+        &self.0
+    }
+
+    /// Consumes the vector, giving back the array of elements.
+    pub fn into_array(self) -> [T; N] {
+        self.0
+    }
+
+    /// Produces a new vector by applying the function to every element.
+    pub fn map<U>(self, func: impl FnMut(T) -> U) -> Vector<U, N> {
+        Vector(self.0.map(func))
+    }
+
+    /// Converts every element into another type.
+    pub fn convert<U: From<T>>(self) -> Vector<U, N> {
+        self.map(U::from)
+    }
+}
+
+impl<T: Copy, const N: usize> Vector<T, N> {
+    /// Multiplies every element by the given scalar.
+    pub fn scale(&self, scalar: T) -> Self
+    where
+        T: Mul<Output = T>,
+    {
+        Vector(self.0.map(|value| value * scalar))
+    }
+
+    /// Calculates the dot product of the two vectors.
+    pub fn dot(&self, rhs: &Self) -> T
+    where
+        T: Add<Output = T> + Mul<Output = T> + ZeroOne,
+    {
+        self.0
+            .iter()
+            .zip(rhs.0.iter())
+            .fold(T::ZERO, |total, (a, b)| total + *a * *b)
+    }
+}
+
+impl<T: Copy + Neg<Output = T> + PartialOrd + ZeroOne, const N: usize> Vector<T, N> {
+    /// Takes the absolute value of every element.
+    pub fn abs(&self) -> Self {
+        Vector(
+            self.0
+                .map(|value| if value < T::ZERO { -value } else { value }),
+        )
+    }
+
+    /// Replaces every element by -1, 0 or 1 depending on its sign.
+    pub fn signum(&self) -> Self {
+        Vector(self.0.map(|value| {
+            if value < T::ZERO {
+                -T::ONE
+            } else if value > T::ZERO {
+                T::ONE
+            } else {
+                T::ZERO
+            }
+        }))
+    }
+
+    /// Calculates the sum of the absolute values of the elements.
+    pub fn manhattan_norm(&self) -> T
+    where
+        T: Add<Output = T>,
+    {
+        self.abs()
+            .0
+            .into_iter()
+            .fold(T::ZERO, |total, value| total + value)
+    }
+
+    /// Calculates the largest of the absolute values of the elements.
+    pub fn chebyshev_norm(&self) -> T {
+        self.abs()
+            .0
+            .into_iter()
+            .fold(T::ZERO, |max, value| if value > max { value } else { max })
+    }
+
+    /// Calculates the manhattan distance between the two points.
+    pub fn manhattan_distance(&self, other: &Self) -> T
+    where
+        T: Add<Output = T> + Sub<Output = T>,
+    {
+        (self - other).manhattan_norm()
+    }
+
+    /// Calculates the chebyshev distance between the two points.
+    pub fn chebyshev_distance(&self, other: &Self) -> T
+    where
+        T: Sub<Output = T>,
+    {
+        (self - other).chebyshev_norm()
+    }
+}
+
+impl<T: Add<Output = T> + Copy + Mul<Output = T> + ZeroOne, const N: usize> Vector<T, N> {
+    /// Calculates the square of the length of the vector, which unlike the length itself can
+    /// be calculated exactly for integers.
+    pub fn squared_euclidean_norm(&self) -> T {
+        self.dot(self)
+    }
+
+    /// Calculates the length of the vector.
+    pub fn euclidean_norm(&self) -> f64
+    where
+        T: ToF64,
+    {
+        self.squared_euclidean_norm().to_f64().sqrt()
+    }
+
+    /// Calculates the square of the straight line distance between the two points.
+    pub fn squared_euclidean_distance(&self, other: &Self) -> T
+    where
+        T: Sub<Output = T>,
+    {
+        (self - other).squared_euclidean_norm()
+    }
+}
+
+impl<T: Copy> Vector<T, 2> {
+    /// Gets the x element of the vector.
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    /// Gets the y element of the vector.
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+}
+
+impl<T: Copy> Vector<T, 3> {
+    /// Gets the x element of the vector.
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    /// Gets the y element of the vector.
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    /// Gets the z element of the vector.
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(value: [T; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> Add for Vector<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vector(from_fn(|idx| self.0[idx] + rhs.0[idx]))
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> Add<&Vector<T, N>> for &Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn add(self, rhs: &Vector<T, N>) -> Self::Output {
+        *self + *rhs
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> AddAssign<&Vector<T, N>> for Vector<T, N> {
+    fn add_assign(&mut self, rhs: &Vector<T, N>) {
+        *self = *self + *rhs;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const N: usize> Sub for Vector<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector(from_fn(|idx| self.0[idx] - rhs.0[idx]))
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const N: usize> Sub<&Vector<T, N>> for &Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn sub(self, rhs: &Vector<T, N>) -> Self::Output {
+        *self - *rhs
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const N: usize> SubAssign<&Vector<T, N>> for Vector<T, N> {
+    fn sub_assign(&mut self, rhs: &Vector<T, N>) {
+        *self = *self - *rhs;
+    }
+}
+
+impl<T: Neg<Output = T>, const N: usize> Neg for Vector<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(T::neg)
+    }
+}
+
+// Parses a vector from its comma separated elements, e.g. "1,-2,3".
+impl<T: FromStr, const N: usize> FromStr for Vector<T, N> {
+    type Err = ParseVectorError<T::Err>;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let elements = string
+            .trim()
+            .split(',')
+            .map(|element| element.trim().parse::<T>())
+            .collect::<Vec<_>>();
+        if elements.len() < N {
+            Err(ParseVectorError::NotEnoughParts)
+        } else if elements.len() > N {
+            Err(ParseVectorError::TooManyParts)
+        } else {
+            let elements = elements
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(ParseVectorError::ParseElementError)?;
+            Ok(Self(elements.try_into().ok().expect("length was checked")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_arithmetic() {
+        let mut a = Vector::new([1, -2, 3]);
+        let b = Vector::new([-3, 5, 4]);
+        assert_eq!(a + b, Vector::new([-2, 3, 7]));
+        assert_eq!(a - b, Vector::new([4, -7, -1]));
+        assert_eq!(-a, Vector::new([-1, 2, -3]));
+        assert_eq!(a.scale(2), Vector::new([2, -4, 6]));
+        a += &b;
+        assert_eq!(a, Vector::new([-2, 3, 7]));
+        a -= &b;
+        assert_eq!(a, Vector::new([1, -2, 3]));
+    }
+
+    #[test]
+    fn test_vector_indexing() {
+        let mut vector = Vector::new([4, 5]);
+        vector[1] = 7;
+        assert_eq!((vector[0], vector.x(), vector.y()), (4, 4, 7));
+        assert_eq!(Vector::new([1, 2, 3]).z(), 3);
+        assert_eq!(vector.into_array(), [4, 7]);
+    }
+
+    #[test]
+    fn test_vector_dot() {
+        assert_eq!(Vector::new([1, -2, 3]).dot(&Vector::new([-3, 5, 4])), -1);
+    }
+
+    #[test]
+    fn test_vector_norms() {
+        let vector = Vector::new([1, -7, 3]);
+        assert_eq!(vector.abs(), Vector::new([1, 7, 3]));
+        assert_eq!(vector.signum(), Vector::new([1, -1, 1]));
+        assert_eq!(Vector::new([0, -4]).signum(), Vector::new([0, -1]));
+        assert_eq!(vector.manhattan_norm(), 11);
+        assert_eq!(vector.chebyshev_norm(), 7);
+        assert_eq!(vector.squared_euclidean_norm(), 59);
+        assert_eq!(Vector::new([3, -4]).euclidean_norm(), 5.0);
+        assert_eq!(Vector::<i64, 3>::new([2, -3, 6]).euclidean_norm(), 7.0);
+        assert_eq!(Vector::<usize, 2>::new([5, 12]).euclidean_norm(), 13.0);
+        assert_eq!(Vector::new([0.6, 0.8]).euclidean_norm(), 1.0);
+    }
+
+    #[test]
+    fn test_vector_distances() {
+        let p1 = Vector::new([1, -2, 3]);
+        let p2 = Vector::new([-3, 5, 4]);
+        assert_eq!(p1.manhattan_distance(&p2), 12);
+        assert_eq!(p1.chebyshev_distance(&p2), 7);
+        assert_eq!(p1.squared_euclidean_distance(&p2), 66);
+    }
+
+    #[test]
+    fn test_vector_convert() {
+        assert_eq!(
+            Vector::new([1u32, 2]).convert::<i64>(),
+            Vector::new([1i64, 2])
+        );
+    }
+
+    #[test]
+    fn test_vector_from_str_not_enough_parts() {
+        assert!(matches!(
+            "17".parse::<Vector<u32, 2>>(),
+            Err(ParseVectorError::NotEnoughParts)
+        ));
+    }
+
+    #[test]
+    fn test_vector_from_str_too_many_parts() {
+        assert!(matches!(
+            "17, 21, 22".parse::<Vector<u32, 2>>(),
+            Err(ParseVectorError::TooManyParts)
+        ));
+    }
+
+    #[test]
+    fn test_vector_from_str_parse_element_error() {
+        assert!(matches!(
+            "17, 2i".parse::<Vector<u32, 2>>(),
+            Err(ParseVectorError::ParseElementError(_))
+        ));
+    }
+
+    #[test]
+    fn test_vector_from_str_success() {
+        assert_eq!(
+            "17, 21".parse::<Vector<u32, 2>>().unwrap(),
+            Vector::new([17, 21])
+        );
+        assert_eq!(
+            "-618,-824,-621".parse::<Vector<i64, 3>>().unwrap(),
+            Vector::new([-618, -824, -621])
+        );
+    }
+}
@@ -1,5 +1,9 @@
 pub mod binary;
 pub mod collections;
+pub mod cycles;
+pub mod graph;
 pub mod iter;
 pub mod math;
+pub mod netpbm;
 pub mod num;
+pub mod ocr;
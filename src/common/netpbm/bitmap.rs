@@ -0,0 +1,28 @@
+use {
+    super::write_plain,
+    std::io::{Result, Write},
+};
+
+/// A rectangular grid where every pixel is either on or off, which can be written out as a
+/// plain PBM image. Pixels which are on are written as 1, which PBM viewers show as black.
+pub trait Bitmap {
+    /// Gets the number of columns in the grid.
+    fn width(&self) -> usize;
+
+    /// Gets the number of rows in the grid.
+    fn height(&self) -> usize;
+
+    /// Gets whether the pixel at the given column and row is on.
+    fn pixel(&self, col: usize, row: usize) -> bool;
+
+    /// Writes the grid as a plain (P1) PBM image.
+    fn write_pbm(&self, writer: &mut impl Write) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let header = format!("P1\n{} {}", self.width(), self.height());
+        write_plain(writer, &header, self.width(), self.height(), |col, row| {
+            self.pixel(col, row) as u16
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use {
+    super::write_plain,
+    std::io::{Result, Write},
+};
+
+/// A rectangular grid where every pixel holds a small integer, which can be written out as a
+/// plain PGM image. PGM viewers show 0 as black and max_value as white.
+pub trait Graymap {
+    /// Gets the number of columns in the grid.
+    fn width(&self) -> usize;
+
+    /// Gets the number of rows in the grid.
+    fn height(&self) -> usize;
+
+    /// Gets the largest value that any pixel can have.
+    fn max_value(&self) -> u16;
+
+    /// Gets the value of the pixel at the given column and row, which is never more than
+    /// max_value.
+    fn level(&self, col: usize, row: usize) -> u16;
+
+    /// Writes the grid as a plain (P2) PGM image.
+    fn write_pgm(&self, writer: &mut impl Write) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let header = format!(
+            "P2\n{} {}\n{}",
+            self.width(),
+            self.height(),
+            self.max_value()
+        );
+        write_plain(writer, &header, self.width(), self.height(), |col, row| {
+            self.level(col, row)
+        })
+    }
+}
@@ -0,0 +1,45 @@
+mod bitmap;
+mod graymap;
+mod netpbm_image;
+mod parse_netpbm_error;
+
+pub use {
+    bitmap::Bitmap, graymap::Graymap, netpbm_image::NetpbmImage,
+    parse_netpbm_error::ParseNetpbmError,
+};
+
+use std::io::{Result, Write};
+
+// Plain Netpbm files aren't meant to have lines longer than this.
+const MAX_LINE_LENGTH: usize = 70;
+
+// Writes the header and then the value of every pixel, separated by spaces. Each row of the
+// image starts on a new line, and long rows are wrapped to stay under the line length limit.
+fn write_plain(
+    writer: &mut impl Write,
+    header: &str,
+    width: usize,
+    height: usize,
+    value: impl Fn(usize, usize) -> u16,
+) -> Result<()> {
+    writeln!(writer, "{}", header)?;
+    (0..height).try_for_each(|row| {
+        let mut line_length = 0;
+        (0..width).try_for_each(|col| {
+            let value = value(col, row).to_string();
+            let separator = if line_length == 0 {
+                ""
+            } else if line_length + 1 + value.len() > MAX_LINE_LENGTH {
+                "\n"
+            } else {
+                " "
+            };
+            line_length = match separator {
+                "\n" => value.len(),
+                _ => line_length + separator.len() + value.len(),
+            };
+            write!(writer, "{}{}", separator, value)
+        })?;
+        writeln!(writer)
+    })
+}
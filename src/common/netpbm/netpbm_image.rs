@@ -0,0 +1,268 @@
+use {
+    super::{Bitmap, Graymap, ParseNetpbmError},
+    std::str::FromStr,
+};
+
+/// An image read from a plain PBM or PGM file. PBM images are read in as having a max_value
+/// of 1, so either kind can be used as a Bitmap (where any non zero pixel is on) or a Graymap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetpbmImage {
+    width: usize,
+    height: usize,
+    max_value: u16,
+    levels: Vec<u16>,
+}
+
+impl NetpbmImage {
+    /// Gets the number of columns in the image.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the number of rows in the image.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl Bitmap for NetpbmImage {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, col: usize, row: usize) -> bool {
+        self.levels[row * self.width + col] != 0
+    }
+}
+
+impl Graymap for NetpbmImage {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn max_value(&self) -> u16 {
+        self.max_value
+    }
+
+    fn level(&self, col: usize, row: usize) -> u16 {
+        self.levels[row * self.width + col]
+    }
+}
+
+// Parses a P1 (PBM) or P2 (PGM) image, where everything from a '#' to the end of the line is
+// a comment. The pixels of a PBM image don't need any whitespace between them.
+impl FromStr for NetpbmImage {
+    type Err = ParseNetpbmError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut tokens = string
+            .lines()
+            .flat_map(|line| line.split('#').next().unwrap_or("").split_whitespace());
+        let magic_number = tokens.next().ok_or(ParseNetpbmError::MissingHeader)?;
+        let is_bitmap = match magic_number {
+            "P1" => true,
+            "P2" => false,
+            _ => {
+                return Err(ParseNetpbmError::UnsupportedFormat {
+                    magic_number: magic_number.to_string(),
+                })
+            }
+        };
+        let mut header_value = || {
+            tokens
+                .next()
+                .ok_or(ParseNetpbmError::MissingHeader)?
+                .parse::<usize>()
+                .map_err(ParseNetpbmError::ParseIntError)
+        };
+        let (width, height) = (header_value()?, header_value()?);
+        let pixel_count = width
+            .checked_mul(height)
+            .ok_or(ParseNetpbmError::TooManyPixels { width, height })?;
+        let max_value = if is_bitmap {
+            1
+        } else {
+            match u16::try_from(header_value()?) {
+                Ok(max_value) if max_value > 0 => max_value,
+                _ => return Err(ParseNetpbmError::InvalidMaxValue),
+            }
+        };
+
+        let levels = if is_bitmap {
+            tokens
+                .flat_map(|token| token.split_inclusive(|_| true))
+                .map(|digit| digit.parse::<u16>())
+                .collect::<Result<Vec<_>, _>>()
+        } else {
+            tokens.map(|token| token.parse::<u16>()).collect()
+        }
+        .map_err(ParseNetpbmError::ParseIntError)?;
+        if let Some(value) = levels.iter().find(|value| **value > max_value) {
+            return Err(ParseNetpbmError::ValueOutOfRange {
+                value: *value,
+                max_value,
+            });
+        }
+        if levels.len() != pixel_count {
+            return Err(ParseNetpbmError::WrongPixelCount {
+                expected: pixel_count,
+                found: levels.len(),
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            max_value,
+            levels,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A grid with every value from 0 to 5, used to check the writers.
+    struct Ramp(usize);
+
+    impl Graymap for Ramp {
+        fn width(&self) -> usize {
+            self.0
+        }
+
+        fn height(&self) -> usize {
+            2
+        }
+
+        fn max_value(&self) -> u16 {
+            5
+        }
+
+        fn level(&self, col: usize, row: usize) -> u16 {
+            ((row * self.0 + col) % 6) as u16
+        }
+    }
+
+    impl Bitmap for Ramp {
+        fn width(&self) -> usize {
+            self.0
+        }
+
+        fn height(&self) -> usize {
+            2
+        }
+
+        fn pixel(&self, col: usize, row: usize) -> bool {
+            (col + row).is_multiple_of(2)
+        }
+    }
+
+    fn write(func: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>) -> String {
+        let mut output = vec![];
+        func(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_write_pbm() {
+        let output = write(|writer| Ramp(3).write_pbm(writer));
+        assert_eq!(output, "P1\n3 2\n1 0 1\n0 1 0\n");
+    }
+
+    #[test]
+    fn test_write_pgm() {
+        let output = write(|writer| Ramp(3).write_pgm(writer));
+        assert_eq!(output, "P2\n3 2\n5\n0 1 2\n3 4 5\n");
+    }
+
+    #[test]
+    fn test_write_wraps_long_rows() {
+        let output = write(|writer| Ramp(100).write_pbm(writer));
+        assert!(output.lines().all(|line| line.len() <= 70));
+        assert_eq!(output.lines().count(), 2 + 2 * 3);
+    }
+
+    #[test]
+    fn test_write_then_parse() {
+        let ramp = Ramp(100);
+        let image = write(|writer| ramp.write_pgm(writer))
+            .parse::<NetpbmImage>()
+            .unwrap();
+        assert_eq!((image.width(), image.height()), (100, 2));
+        assert_eq!(image.max_value(), 5);
+        assert!((0..2).all(|row| (0..100).all(|col| image.level(col, row) == ramp.level(col, row))));
+
+        let image = write(|writer| ramp.write_pbm(writer))
+            .parse::<NetpbmImage>()
+            .unwrap();
+        assert_eq!(image.max_value(), 1);
+        assert!((0..2).all(|row| (0..100).all(|col| image.pixel(col, row) == ramp.pixel(col, row))));
+    }
+
+    #[test]
+    fn test_parse_comments_and_packed_bits() {
+        let image = "P1 # a comment\n# another\n3 2\n101\n01 0"
+            .parse::<NetpbmImage>()
+            .unwrap();
+        assert_eq!(image.levels, vec![1, 0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "P3\n1 1\n255\n0 0 0".parse::<NetpbmImage>(),
+            Err(ParseNetpbmError::UnsupportedFormat {
+                magic_number: "P3".to_string()
+            })
+        );
+        assert_eq!(
+            "".parse::<NetpbmImage>(),
+            Err(ParseNetpbmError::MissingHeader)
+        );
+        assert_eq!(
+            "P2\n2 2".parse::<NetpbmImage>(),
+            Err(ParseNetpbmError::MissingHeader)
+        );
+        assert!(matches!(
+            "P1\n2 x\n".parse::<NetpbmImage>(),
+            Err(ParseNetpbmError::ParseIntError(_))
+        ));
+        assert_eq!(
+            "P2\n1 1\n0\n0".parse::<NetpbmImage>(),
+            Err(ParseNetpbmError::InvalidMaxValue)
+        );
+        assert_eq!(
+            "P2\n1 1\n70000\n0".parse::<NetpbmImage>(),
+            Err(ParseNetpbmError::InvalidMaxValue)
+        );
+        assert_eq!(
+            "P1\n2 1\n1 2".parse::<NetpbmImage>(),
+            Err(ParseNetpbmError::ValueOutOfRange {
+                value: 2,
+                max_value: 1
+            })
+        );
+        assert_eq!(
+            format!("P1\n{} 2\n1", usize::MAX).parse::<NetpbmImage>(),
+            Err(ParseNetpbmError::TooManyPixels {
+                width: usize::MAX,
+                height: 2
+            })
+        );
+        assert_eq!(
+            "P1\n2 2\n1 0 1".parse::<NetpbmImage>(),
+            Err(ParseNetpbmError::WrongPixelCount {
+                expected: 4,
+                found: 3
+            })
+        );
+    }
+}
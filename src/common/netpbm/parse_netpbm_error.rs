@@ -0,0 +1,49 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result},
+    num::ParseIntError,
+};
+
+/// Error cases for parsing a plain PBM or PGM image.
+#[derive(Debug, PartialEq)]
+pub enum ParseNetpbmError {
+    UnsupportedFormat { magic_number: String },
+    MissingHeader,
+    ParseIntError(ParseIntError),
+    TooManyPixels { width: usize, height: usize },
+    InvalidMaxValue,
+    ValueOutOfRange { value: u16, max_value: u16 },
+    WrongPixelCount { expected: usize, found: usize },
+}
+
+impl Display for ParseNetpbmError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::UnsupportedFormat { magic_number } => write!(
+                f,
+                "ParseNetpbmError::UnsupportedFormat {{ magic_number: {} }}",
+                magic_number
+            ),
+            Self::MissingHeader => write!(f, "ParseNetpbmError::MissingHeader"),
+            Self::ParseIntError(error) => write!(f, "ParseNetpbmError::ParseIntError({})", error),
+            Self::TooManyPixels { width, height } => write!(
+                f,
+                "ParseNetpbmError::TooManyPixels {{ width: {}, height: {} }}",
+                width, height
+            ),
+            Self::InvalidMaxValue => write!(f, "ParseNetpbmError::InvalidMaxValue"),
+            Self::ValueOutOfRange { value, max_value } => write!(
+                f,
+                "ParseNetpbmError::ValueOutOfRange {{ value: {}, max_value: {} }}",
+                value, max_value
+            ),
+            Self::WrongPixelCount { expected, found } => write!(
+                f,
+                "ParseNetpbmError::WrongPixelCount {{ expected: {}, found: {} }}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl Error for ParseNetpbmError {}
@@ -0,0 +1,50 @@
+/// Division which only gives a result when it's exact, so integer division never rounds.
+pub trait ExactDiv: Sized {
+    /// Divides by the divisor, or gives None if it's zero or doesn't divide exactly.
+    fn exact_div(self, divisor: Self) -> Option<Self>;
+}
+
+macro_rules! impl_exact_div_int {
+    ($($t:ty),*) => {
+        $(
+            impl ExactDiv for $t {
+                fn exact_div(self, divisor: Self) -> Option<Self> {
+                    self.checked_rem(divisor)
+                        .filter(|remainder| *remainder == 0)
+                        .and_then(|_| self.checked_div(divisor))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_exact_div_float {
+    ($($t:ty),*) => {
+        $(
+            impl ExactDiv for $t {
+                fn exact_div(self, divisor: Self) -> Option<Self> {
+                    (divisor != 0.0).then(|| self / divisor)
+                }
+            }
+        )*
+    };
+}
+
+impl_exact_div_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_exact_div_float!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_div() {
+        assert_eq!(12.exact_div(4), Some(3));
+        assert_eq!((-12).exact_div(4), Some(-3));
+        assert_eq!(13.exact_div(4), None);
+        assert_eq!(1.exact_div(0), None);
+        assert_eq!(i8::MIN.exact_div(-1), None);
+        assert_eq!(3.0.exact_div(2.0), Some(1.5));
+        assert_eq!(1.0.exact_div(0.0), None);
+    }
+}
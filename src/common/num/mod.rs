@@ -1,3 +1,5 @@
+mod exact_div;
+mod to_f64;
 mod zero_one;
 
-pub use zero_one::ZeroOne;
+pub use {exact_div::ExactDiv, to_f64::ToF64, zero_one::ZeroOne};
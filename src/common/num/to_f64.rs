@@ -0,0 +1,20 @@
+/// Converts a numeric type to the nearest f64, for calculations like square roots which need
+/// floating point. Unlike Into<f64> this is available for 64 bit and larger integers, which
+/// can lose precision when they're very large.
+pub trait ToF64 {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($t:ty),*) => {
+        $(
+            impl ToF64 for $t {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_to_f64!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
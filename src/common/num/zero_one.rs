@@ -1,4 +1,19 @@
+/// Gives access to the additive and multiplicative identities of a numeric type.
 pub trait ZeroOne {
     const ZERO: Self;
     const ONE: Self;
 }
+
+macro_rules! impl_zero_one {
+    ($zero:literal, $one:literal, $($t:ty),*) => {
+        $(
+            impl ZeroOne for $t {
+                const ZERO: Self = $zero;
+                const ONE: Self = $one;
+            }
+        )*
+    };
+}
+
+impl_zero_one!(0, 1, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_zero_one!(0.0, 1.0, f32, f64);
@@ -0,0 +1,169 @@
+mod read_text_error;
+
+pub use read_text_error::ReadTextError;
+
+use super::netpbm::Bitmap;
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+// Each glyph is followed by a blank column before the next one starts.
+const GLYPH_STRIDE: usize = GLYPH_WIDTH + 1;
+
+// The capital letters of the font that puzzles draw their answers in, with the rows of each
+// glyph joined together.
+const FONT: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// Reads the capital letters drawn in the bitmap, using the 4x6 font that puzzle answers are
+/// drawn in. The text can be anywhere in the bitmap since only the smallest box around the
+/// pixels which are on is read, which needs to be exactly the height of a glyph. The glyphs
+/// are read left to right, each followed by a single blank column.
+pub fn read_text(bitmap: &impl Bitmap) -> Result<String, ReadTextError> {
+    let lit = (0..bitmap.height())
+        .flat_map(|row| (0..bitmap.width()).map(move |col| (col, row)))
+        .filter(|(col, row)| bitmap.pixel(*col, *row))
+        .collect::<Vec<_>>();
+    if lit.is_empty() {
+        return Err(ReadTextError::Empty);
+    }
+    let (left, right, top, bottom) = lit.iter().fold(
+        (usize::MAX, 0, usize::MAX, 0),
+        |(left, right, top, bottom), (col, row)| {
+            (
+                left.min(*col),
+                right.max(*col),
+                top.min(*row),
+                bottom.max(*row),
+            )
+        },
+    );
+    let height = bottom - top + 1;
+    if height != GLYPH_HEIGHT {
+        return Err(ReadTextError::InvalidHeight { height });
+    }
+
+    let pixel = |col: usize, row: usize| col < bitmap.width() && bitmap.pixel(col, top + row);
+    (left..=right)
+        .step_by(GLYPH_STRIDE)
+        .enumerate()
+        .map(|(index, column)| {
+            let glyph = (0..GLYPH_HEIGHT)
+                .flat_map(|row| (0..GLYPH_WIDTH).map(move |col| (col, row)))
+                .map(|(col, row)| if pixel(column + col, row) { '#' } else { '.' })
+                .collect::<String>();
+            FONT.iter()
+                .find(|(_, pattern)| *pattern == glyph)
+                .map(|(letter, _)| *letter)
+                .ok_or(ReadTextError::UnknownGlyph { index, column })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A bitmap drawn with the same characters as the font.
+    struct Drawing(Vec<Vec<bool>>);
+
+    impl Drawing {
+        fn parse(lines: &[String]) -> Self {
+            Self(
+                lines
+                    .iter()
+                    .map(|line| line.chars().map(|c| c == '#').collect())
+                    .collect(),
+            )
+        }
+    }
+
+    impl Bitmap for Drawing {
+        fn width(&self) -> usize {
+            self.0.first().map_or(0, Vec::len)
+        }
+
+        fn height(&self) -> usize {
+            self.0.len()
+        }
+
+        fn pixel(&self, col: usize, row: usize) -> bool {
+            self.0[row][col]
+        }
+    }
+
+    // Draws the rows of the text with a gap after each glyph, inside a border of blank pixels.
+    fn draw(text: &str) -> Vec<String> {
+        let blank = ".".repeat(text.len() * GLYPH_STRIDE + 2);
+        let rows = (0..GLYPH_HEIGHT).map(|row| {
+            let glyphs = text.chars().map(|letter| {
+                let (_, pattern) = FONT.iter().find(|(c, _)| *c == letter).unwrap();
+                format!("{}.", &pattern[row * GLYPH_WIDTH..(row + 1) * GLYPH_WIDTH])
+            });
+            format!(".{}.", glyphs.collect::<String>())
+        });
+        std::iter::once(blank.clone())
+            .chain(rows)
+            .chain(std::iter::once(blank))
+            .collect()
+    }
+
+    #[test]
+    fn test_read_every_letter() {
+        let alphabet = FONT.iter().map(|(letter, _)| *letter).collect::<String>();
+        assert_eq!(read_text(&Drawing::parse(&draw(&alphabet))), Ok(alphabet));
+    }
+
+    #[test]
+    fn test_read_text_puzzle_code() {
+        let drawing = Drawing::parse(&draw("RZKZLPGH"));
+        assert_eq!(read_text(&drawing), Ok("RZKZLPGH".to_string()));
+    }
+
+    #[test]
+    fn test_read_text_empty() {
+        assert_eq!(
+            read_text(&Drawing::parse(&draw(""))),
+            Err(ReadTextError::Empty)
+        );
+    }
+
+    #[test]
+    fn test_read_text_invalid_height() {
+        let mut lines = draw("E");
+        lines[0].replace_range(1..2, "#");
+        assert_eq!(
+            read_text(&Drawing::parse(&lines)),
+            Err(ReadTextError::InvalidHeight { height: 7 })
+        );
+    }
+
+    #[test]
+    fn test_read_text_unknown_glyph() {
+        // take the bottom right pixel off the A, which is the third glyph.
+        let mut lines = draw("HZA");
+        lines[6].replace_range(14..15, ".");
+        assert_eq!(
+            read_text(&Drawing::parse(&lines)),
+            Err(ReadTextError::UnknownGlyph {
+                index: 2,
+                column: 11
+            })
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Error cases for reading the letters drawn in a bitmap.
+#[derive(Debug, PartialEq)]
+pub enum ReadTextError {
+    Empty,
+    InvalidHeight { height: usize },
+    UnknownGlyph { index: usize, column: usize },
+}
+
+impl Display for ReadTextError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Empty => write!(f, "ReadTextError::Empty"),
+            Self::InvalidHeight { height } => {
+                write!(f, "ReadTextError::InvalidHeight {{ height: {} }}", height)
+            }
+            Self::UnknownGlyph { index, column } => write!(
+                f,
+                "ReadTextError::UnknownGlyph {{ index: {}, column: {} }}",
+                index, column
+            ),
+        }
+    }
+}
+
+impl Error for ReadTextError {}
@@ -1,4 +1,4 @@
-use super::{delta::Delta, line_segment::LineSegment, point::Point};
+use super::{line_segment::LineSegment, Point};
 
 /// An enumeration that allows a line segment to be classified for later interpretation.
 /// In part 1 this classification is just horizontal or vertical.
@@ -10,11 +10,9 @@ pub enum ClassifiedLineSegment {
 
 impl From<LineSegment> for ClassifiedLineSegment {
     fn from(value: LineSegment) -> Self {
-        let delta: Delta = (&value).into();
-        let delta: (i64, i64) = (&delta).into();
-        match delta {
-            (_, 0) => ClassifiedLineSegment::Horizontal(value),
-            (0, _) => ClassifiedLineSegment::Vertical(value),
+        match value.delta().into_array() {
+            [_, 0] => ClassifiedLineSegment::Horizontal(value),
+            [0, _] => ClassifiedLineSegment::Vertical(value),
             _ => ClassifiedLineSegment::Diagonal(value),
         }
     }
@@ -1,4 +1,4 @@
-use super::{classified_line_segment::ClassifiedLineSegment, delta::Delta, point::Point};
+use super::{classified_line_segment::ClassifiedLineSegment, Delta, Point};
 
 /// Struct which will produce points along the length of a classified line segment.
 /// This is fairly easy to do because we know that the line is classified as either
@@ -14,8 +14,7 @@ pub struct ClassifiedLineSegmentIter {
 impl ClassifiedLineSegmentIter {
     pub fn new(from: &ClassifiedLineSegment) -> Self {
         let (current, end): (Point, Point) = from.into();
-        let mut delta = &end - &current;
-        delta.normalize();
+        let delta = (end.convert::<i64>() - current.convert::<i64>()).signum();
 
         Self {
             current,
@@ -33,11 +32,12 @@ impl Iterator for ClassifiedLineSegmentIter {
         if self.exhausted {
             None
         } else {
-            let current = self.current.clone();
+            let current = self.current;
             if self.current == self.end {
                 self.exhausted = true;
             } else {
-                self.current += &self.delta;
+                self.current =
+                    (self.current.convert::<i64>() + self.delta).map(|coord| coord as u32);
             }
             Some(current)
         }
@@ -1,5 +1,5 @@
 use {
-    super::{parse_line_error::ParseLineError, point::Point},
+    super::{parse_line_error::ParseLineError, Delta, Point},
     std::str::FromStr,
 };
 
@@ -31,16 +31,22 @@ impl FromStr for LineSegment {
     }
 }
 
+impl LineSegment {
+    /// Gets the delta which takes the first point of the line onto the second.
+    pub fn delta(&self) -> Delta {
+        self.1.convert::<i64>() - self.0.convert::<i64>()
+    }
+}
+
 impl From<&LineSegment> for (Point, Point) {
     fn from(value: &LineSegment) -> Self {
-        (value.0.clone(), value.1.clone())
+        (value.0, value.1)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::super::delta::Delta;
-    use super::*;
+    use {super::*, crate::common::math::Vector};
 
     #[test]
     fn test_line_segment_from_str_parse_point_error() {
@@ -79,8 +85,8 @@ mod tests {
         let line_segment = "25,13 -> 92,3".parse::<LineSegment>().unwrap();
         let point_1 = "25,13".parse::<Point>().unwrap();
         let point_2 = "92,3".parse::<Point>().unwrap();
-        let expected = &point_2 - &point_1;
-        let calculated: Delta = (&line_segment).into();
-        assert_eq!(expected, calculated);
+        let expected = point_2.convert::<i64>() - point_1.convert::<i64>();
+        assert_eq!(line_segment.delta(), expected);
+        assert_eq!(line_segment.delta(), Vector::new([67, -10]));
     }
 }
@@ -1,12 +1,10 @@
 mod classified_line_segment;
 mod classified_line_segment_iter;
-mod delta;
 mod line_segment;
 mod parse_line_error;
-mod parse_point_error;
-mod point;
 
 use {
+    crate::common::math::Vector,
     classified_line_segment::ClassifiedLineSegment,
     classified_line_segment_iter::ClassifiedLineSegmentIter,
     line_segment::LineSegment,
@@ -17,6 +15,13 @@ use {
     },
 };
 
+/// An x and y coordinate representing an individual point.
+type Point = Vector<u32, 2>;
+
+/// The signed delta between two points. Since the points contain u32 coordinates, we represent
+/// the delta with i64 as it's large enough to contain the positive range of u32's.
+type Delta = Vector<i64, 2>;
+
 pub fn run(input: &str) -> (impl Display, impl Display) {
     let input = input.as_bytes();
     let reader = BufReader::new(input);
@@ -46,18 +51,18 @@ fn load_line_segments(reader: impl BufRead) -> Vec<ClassifiedLineSegment> {
 /// Creates a HashMap of point to count from a set of classified lines.
 fn get_point_counts<'a>(
     segments: impl Iterator<Item = &'a ClassifiedLineSegment>,
-) -> HashMap<(u32, u32), u32> {
+) -> HashMap<Point, u32> {
     let mut hm = HashMap::new();
     segments.for_each(|line| {
         ClassifiedLineSegmentIter::new(line).for_each(|point| {
-            *hm.entry((&point).into()).or_insert(0) += 1;
+            *hm.entry(point).or_insert(0) += 1;
         });
     });
     hm
 }
 
 /// Gets the number of intersection points.
-fn count_intersections(counts: &HashMap<(u32, u32), u32>) -> usize {
+fn count_intersections(counts: &HashMap<Point, u32>) -> usize {
     counts.into_iter().filter(|(_, count)| **count >= 2).count()
 }
 
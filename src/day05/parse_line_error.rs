@@ -1,15 +1,15 @@
-use super::parse_point_error::ParsePointError;
+use {crate::common::math::ParseVectorError, std::num::ParseIntError};
 
 /// Enum with the error conditions for parsing a line from string
 #[derive(Debug)]
 pub enum ParseLineError {
-    ParsePointError(ParsePointError),
+    ParsePointError(ParseVectorError<ParseIntError>),
     NotEnoughParts,
     TooManyParts,
 }
 
-impl From<ParsePointError> for ParseLineError {
-    fn from(value: ParsePointError) -> Self {
+impl From<ParseVectorError<ParseIntError>> for ParseLineError {
+    fn from(value: ParseVectorError<ParseIntError>) -> Self {
         Self::ParsePointError(value)
     }
 }
@@ -1,12 +1,16 @@
-use std::{
-    collections::HashMap,
-    fmt::Display,
-    io::{BufRead, BufReader},
-    num::ParseIntError,
+use {
+    crate::common::cycles::find_cycle,
+    std::{
+        collections::HashMap,
+        fmt::Display,
+        io::{BufRead, BufReader},
+        num::ParseIntError,
+    },
 };
 
 const RESET_TO: u8 = 6;
 const SPAWN_AT: u8 = 8;
+const MAX_CYCLE_DAYS: usize = 10_000_000;
 
 pub fn run(input: &str) -> (impl Display, impl Display) {
     let input = input.as_bytes();
@@ -20,6 +24,29 @@ pub fn run(input: &str) -> (impl Display, impl Display) {
     (part_1, part_2)
 }
 
+/// Counts the fish after the given number of days modulo the modulus, which works for far more
+/// days than could ever be simulated. The total keeps growing, but there are only so many
+/// possible counts for each timer modulo something, so they eventually repeat. Gives None if
+/// they don't repeat within ten million days (the period grows with the modulus). Panics if
+/// any timer is above 8.
+pub fn count_fish_modulo(input: &str, days: usize, modulus: u128) -> Option<u128> {
+    let fish_counts = read_lanternfish_counts(BufReader::new(input.as_bytes()))
+        .expect("Could not read lanternfish counts from input.");
+    // An array of the counts for each timer is much quicker to step than the map.
+    let mut timers = [0; SPAWN_AT as usize + 1];
+    fish_counts
+        .iter()
+        .for_each(|(timer, count)| timers[*timer as usize] = count % modulus);
+    let step = |timers: &mut [u128; SPAWN_AT as usize + 1]| {
+        timers.rotate_left(1);
+        timers[RESET_TO as usize] =
+            (timers[RESET_TO as usize] + timers[SPAWN_AT as usize]) % modulus;
+    };
+    let cycle = find_cycle(&timers, step, |timers| *timers, MAX_CYCLE_DAYS)?;
+    (0..cycle.equivalent_steps(days)).for_each(|_| step(&mut timers));
+    Some(timers.iter().sum::<u128>() % modulus)
+}
+
 fn read_lanternfish_counts(reader: impl BufRead) -> Result<HashMap<u8, u128>, ParseIntError> {
     let mut fish_counts = HashMap::new();
     for line in reader.lines().filter_map(|line| Some(line.ok()?)) {
@@ -112,4 +139,16 @@ mod tests {
             EXPECTED
         );
     }
+
+    #[test]
+    fn test_count_fish_modulo() {
+        const INPUT: &str = "3,4,3,1,2";
+        assert_eq!(count_fish_modulo(INPUT, 80, 1000), Some(934));
+        assert_eq!(count_fish_modulo(INPUT, 256, 1000), Some(539));
+        // The counts modulo 1000 repeat every 241800 days.
+        assert_eq!(
+            count_fish_modulo(INPUT, 256 + 241800 * 4_000_000_000_000, 1000),
+            Some(539)
+        );
+    }
 }
@@ -0,0 +1,27 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Error cases for adding delimiter pairs to a table, where a delimiter would either match
+/// nothing or could be read as more than one thing.
+#[derive(Debug, PartialEq)]
+pub enum BuildDelimiterTableError {
+    EmptyDelimiter,
+    DuplicateDelimiter { delimiter: String },
+}
+
+impl Display for BuildDelimiterTableError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::EmptyDelimiter => write!(f, "BuildDelimiterTableError::EmptyDelimiter"),
+            Self::DuplicateDelimiter { delimiter } => write!(
+                f,
+                "BuildDelimiterTableError::DuplicateDelimiter {{ delimiter: {} }}",
+                delimiter
+            ),
+        }
+    }
+}
+
+impl Error for BuildDelimiterTableError {}
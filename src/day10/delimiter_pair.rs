@@ -0,0 +1,27 @@
+/// A pair of delimiters which open and close a section of a line, along with the scores for
+/// the closer when it's found corrupting a line, and when it's needed to complete a line.
+/// The opener and closer can be more than one character long, and can be the same (such as
+/// quotes), in which case it closes the section if it's the innermost one open.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelimiterPair {
+    pub opener: String,
+    pub closer: String,
+    pub corrupted_score: u64,
+    pub incomplete_score: u64,
+}
+
+impl DelimiterPair {
+    pub fn new(opener: &str, closer: &str, corrupted_score: u64, incomplete_score: u64) -> Self {
+        Self {
+            opener: opener.to_string(),
+            closer: closer.to_string(),
+            corrupted_score,
+            incomplete_score,
+        }
+    }
+
+    /// Checks whether the opener and closer are the same delimiter.
+    pub fn is_symmetric(&self) -> bool {
+        self.opener == self.closer
+    }
+}
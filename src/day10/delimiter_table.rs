@@ -0,0 +1,190 @@
+use {
+    super::{
+        build_delimiter_table_error::BuildDelimiterTableError, delimiter_pair::DelimiterPair,
+        opening_type::OpeningType, token::Token,
+    },
+    std::ptr,
+};
+
+/// The delimiter pairs that the line parser knows about. The default is the four bracket
+/// types from the puzzle with their scores. By default any other characters make a line
+/// invalid, but the table can be set to skip over them so it can check the delimiters in
+/// lines containing other text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelimiterTable {
+    pairs: Vec<DelimiterPair>,
+    skip_other_characters: bool,
+    // The delimiters starting with each byte, as the index of the pair and which of its
+    // delimiters it is, so only those need checking at each position in a line.
+    by_first_byte: Vec<Vec<(usize, OpeningType)>>,
+}
+
+impl DelimiterTable {
+    /// Makes a table without any delimiters.
+    pub fn new() -> Self {
+        Self {
+            pairs: vec![],
+            skip_other_characters: false,
+            by_first_byte: vec![vec![]; 256],
+        }
+    }
+
+    /// Adds the delimiter pair to the table. Fails if either delimiter is empty, or is already
+    /// used by another pair, as then it couldn't be told which pair it belongs to.
+    pub fn with_pair(mut self, pair: DelimiterPair) -> Result<Self, BuildDelimiterTableError> {
+        if pair.opener.is_empty() || pair.closer.is_empty() {
+            return Err(BuildDelimiterTableError::EmptyDelimiter);
+        }
+        if let Some(delimiter) = [&pair.opener, &pair.closer].into_iter().find(|delimiter| {
+            self.pairs
+                .iter()
+                .any(|other| other.opener == **delimiter || other.closer == **delimiter)
+        }) {
+            return Err(BuildDelimiterTableError::DuplicateDelimiter {
+                delimiter: delimiter.clone(),
+            });
+        }
+        let idx = self.pairs.len();
+        self.by_first_byte[pair.opener.as_bytes()[0] as usize].push((idx, OpeningType::Opening));
+        self.by_first_byte[pair.closer.as_bytes()[0] as usize].push((idx, OpeningType::Closing));
+        self.pairs.push(pair);
+        Ok(self)
+    }
+
+    /// Sets whether characters which aren't part of any delimiter are skipped over rather
+    /// than making the line invalid.
+    pub fn skipping_other_characters(mut self, skip_other_characters: bool) -> Self {
+        self.skip_other_characters = skip_other_characters;
+        self
+    }
+
+    pub fn pairs(&self) -> &[DelimiterPair] {
+        &self.pairs
+    }
+
+    pub fn skips_other_characters(&self) -> bool {
+        self.skip_other_characters
+    }
+
+    /// Finds the longest delimiter at the start of the string, if there is one. A symmetric
+    /// delimiter closes its pair if that's the innermost one open, otherwise it opens it.
+    /// The innermost pair must be one from this table.
+    pub fn match_token(
+        &self,
+        string: &str,
+        innermost: Option<&DelimiterPair>,
+    ) -> Option<Token<'_>> {
+        let first_byte = *string.as_bytes().first()?;
+        let mut longest: Option<Token> = None;
+        self.by_first_byte[first_byte as usize]
+            .iter()
+            .for_each(|(idx, opening_type)| {
+                let pair = &self.pairs[*idx];
+                let delimiter = match opening_type {
+                    OpeningType::Opening => &pair.opener,
+                    OpeningType::Closing => &pair.closer,
+                };
+                let closes_innermost = innermost.is_some_and(|innermost| ptr::eq(innermost, pair));
+                let is_allowed = !pair.is_symmetric()
+                    || closes_innermost == (*opening_type == OpeningType::Closing);
+                if is_allowed
+                    && string.starts_with(delimiter.as_str())
+                    && longest
+                        .as_ref()
+                        .is_none_or(|token| delimiter.len() > token.length)
+                {
+                    longest = Some(Token {
+                        opening_type: *opening_type,
+                        pair,
+                        length: delimiter.len(),
+                    });
+                }
+            });
+        longest
+    }
+}
+
+impl Default for DelimiterTable {
+    fn default() -> Self {
+        [
+            DelimiterPair::new("(", ")", 3, 1),
+            DelimiterPair::new("[", "]", 57, 2),
+            DelimiterPair::new("{", "}", 1197, 3),
+            DelimiterPair::new("<", ">", 25137, 4),
+        ]
+        .into_iter()
+        .try_fold(Self::new(), Self::with_pair)
+        .expect("The puzzle's delimiters are all different.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_token_default() {
+        let table = DelimiterTable::default();
+        let tokens = "()[]{}<>"
+            .char_indices()
+            .map(|(idx, _)| {
+                let token = table.match_token(&"()[]{}<>"[idx..], None).unwrap();
+                (token.is_opening_token(), token.pair.corrupted_score)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            vec![
+                (true, 3),
+                (false, 3),
+                (true, 57),
+                (false, 57),
+                (true, 1197),
+                (false, 1197),
+                (true, 25137),
+                (false, 25137)
+            ]
+        );
+        assert_eq!(table.match_token("z", None), None);
+    }
+
+    #[test]
+    fn test_match_token_longest() {
+        let table = DelimiterTable::new()
+            .with_pair(DelimiterPair::new("{", "}", 1, 1))
+            .unwrap()
+            .with_pair(DelimiterPair::new("{{", "}}", 2, 2))
+            .unwrap();
+        let token = table.match_token("{{ name }}", None).unwrap();
+        assert_eq!((token.pair.opener.as_str(), token.length), ("{{", 2));
+        let token = table.match_token("{ name }", None).unwrap();
+        assert_eq!((token.pair.opener.as_str(), token.length), ("{", 1));
+    }
+
+    #[test]
+    fn test_match_token_symmetric() {
+        let table = DelimiterTable::new()
+            .with_pair(DelimiterPair::new("\"", "\"", 1, 1))
+            .unwrap();
+        let quote = &table.pairs()[0];
+        assert!(table.match_token("\"", None).unwrap().is_opening_token());
+        assert!(!table
+            .match_token("\"", Some(quote))
+            .unwrap()
+            .is_opening_token());
+    }
+
+    #[test]
+    fn test_with_pair_errors() {
+        assert_eq!(
+            DelimiterTable::new().with_pair(DelimiterPair::new("", ")", 1, 1)),
+            Err(BuildDelimiterTableError::EmptyDelimiter)
+        );
+        assert_eq!(
+            DelimiterTable::default().with_pair(DelimiterPair::new("/*", ")", 1, 1)),
+            Err(BuildDelimiterTableError::DuplicateDelimiter {
+                delimiter: ")".to_string()
+            })
+        );
+    }
+}
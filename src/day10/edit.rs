@@ -0,0 +1,72 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// A single change to a line, at a (1 based) column of the original line. Inserted text goes
+/// before the character at the column, so inserting at one past the last column appends it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Edit {
+    Insert {
+        column: usize,
+        text: String,
+    },
+    Delete {
+        column: usize,
+        text: String,
+    },
+    Substitute {
+        column: usize,
+        found: String,
+        replacement: String,
+    },
+}
+
+impl Display for Edit {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Insert { column, text } => write!(f, "insert {} at column {}", text, column),
+            Self::Delete { column, text } => write!(f, "delete {} at column {}", text, column),
+            Self::Substitute {
+                column,
+                found,
+                replacement,
+            } => write!(
+                f,
+                "replace {} with {} at column {}",
+                found, replacement, column
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Edit::Insert {
+                column: 5,
+                text: ")".to_string()
+            }
+            .to_string(),
+            "insert ) at column 5"
+        );
+        assert_eq!(
+            Edit::Delete {
+                column: 3,
+                text: "*/".to_string()
+            }
+            .to_string(),
+            "delete */ at column 3"
+        );
+        assert_eq!(
+            Edit::Substitute {
+                column: 2,
+                found: "]".to_string(),
+                replacement: ")".to_string()
+            }
+            .to_string(),
+            "replace ] with ) at column 2"
+        );
+    }
+}
@@ -1,130 +1,243 @@
 use {
-    super::{line_type::LineType, token::Token, token_type::TokenType},
+    super::{delimiter_table::DelimiterTable, line_type::LineType, opener::Opener},
     crate::common::collections::Stack,
+    std::ptr,
 };
 
 /// Allows parsing lines of text into a final LineType. The reason
 /// we use this rather than using the FromStr trait and parse function is so
-/// we can reuse the allocation to track the stack.
-pub struct LineParser<T> {
+/// we can reuse the allocation to track the stack, and so we can parse with any delimiter table.
+pub struct LineParser<'a, T> {
     stack: T,
+    table: &'a DelimiterTable,
 }
 
-impl<T> LineParser<T> {
-    pub fn new(stack: T) -> Self {
-        Self { stack }
+impl<'a, T> LineParser<'a, T> {
+    pub fn new(stack: T, table: &'a DelimiterTable) -> Self {
+        Self { stack, table }
     }
 }
 
 /// Placing a Stack bound on T allows us to use a LineParser with any Stack implementation
-/// and we only have access to the methods of Stack and that's it. It must be a Stack of Tokens
-/// to be usable for parsing.
-impl<S: Stack<TokenType>> LineParser<S> {
-    pub fn parse(&mut self, chars: impl Iterator<Item = char>) -> LineType {
+/// and we only have access to the methods of Stack and that's it. It must be a Stack of
+/// openers to be usable for parsing.
+impl<'a, S: Stack<Opener<'a>>> LineParser<'a, S> {
+    pub fn parse(&mut self, line: &str) -> LineType<'a> {
         self.stack.clear();
-        for c in chars {
-            let token = Token::from(c); // will panic if invalid character
-            let is_opening = token.is_opening_token();
-            let token = token.into_token_type();
-
-            if is_opening {
-                self.stack.push(token);
-            } else {
-                let expected = self.stack.pop().unwrap(); // may panic if a closing token appears before an opening one
-                if expected != token {
-                    return LineType::Corrupted {
-                        expected,
-                        found: token,
+        // Only the innermost opener is needed to match symmetric delimiters, so keep it
+        // out of the stack rather than needing to peek at it.
+        let mut innermost: Option<Opener<'a>> = None;
+        let (mut rest, mut column) = (line, 1);
+        while let Some(c) = rest.chars().next() {
+            let length = match self
+                .table
+                .match_token(rest, innermost.as_ref().map(|opener| opener.pair))
+            {
+                Some(token) if token.is_opening_token() => {
+                    let opener = Opener {
+                        pair: token.pair,
+                        column,
                     };
+                    if let Some(outer) = innermost.replace(opener) {
+                        self.stack.push(outer);
+                    }
+                    token.length
                 }
-            }
+                Some(token) => match innermost.take() {
+                    None => {
+                        return LineType::UnmatchedCloser {
+                            found: token.pair,
+                            column,
+                        }
+                    }
+                    Some(opener) if !ptr::eq(opener.pair, token.pair) => {
+                        return LineType::Corrupted {
+                            expected: opener.pair,
+                            found: token.pair,
+                            column,
+                            opener_column: opener.column,
+                        }
+                    }
+                    Some(_) => {
+                        innermost = self.stack.pop();
+                        token.length
+                    }
+                },
+                None if self.table.skips_other_characters() => c.len_utf8(),
+                None => return LineType::UnknownCharacter { found: c, column },
+            };
+            column += rest[..length].chars().count();
+            rest = &rest[length..];
         }
 
-        if self.stack.len() == 0 {
-            LineType::Complete
-        } else {
-            let mut vec = Vec::with_capacity(self.stack.len());
-            while let Some(token) = self.stack.pop() {
-                vec.push(token);
+        match innermost {
+            None => LineType::Complete,
+            Some(opener) => {
+                let mut vec = Vec::with_capacity(self.stack.len() + 1);
+                vec.push(opener);
+                while let Some(opener) = self.stack.pop() {
+                    vec.push(opener);
+                }
+                LineType::Incomplete(vec)
             }
-            LineType::Incomplete(vec)
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, crate::day10::delimiter_pair::DelimiterPair};
 
     #[test]
     fn test_line_parser_construction() {
-        let parser = LineParser::new(vec![1, 2, 3]);
+        let table = DelimiterTable::default();
+        let parser = LineParser::new(vec![1, 2, 3], &table);
         assert_eq!(parser.stack.len(), 3);
         assert_eq!(parser.stack[0], 1);
         assert_eq!(parser.stack[1], 2);
         assert_eq!(parser.stack[2], 3);
     }
 
+    // Gets the closers still needed to complete the line, ignoring the columns.
+    fn unclosed<'a>(line_type: LineType<'a>) -> Vec<&'a str> {
+        match line_type {
+            LineType::Incomplete(vec) => vec
+                .into_iter()
+                .map(|opener| opener.pair.closer.as_str())
+                .collect(),
+            line_type => panic!("Expected an incomplete line but found {:?}", line_type),
+        }
+    }
+
+    // Gets the expected and found closers and the columns for a corrupted line.
+    fn corrupted<'a>(line_type: LineType<'a>) -> (&'a str, &'a str, usize, usize) {
+        match line_type {
+            LineType::Corrupted {
+                expected,
+                found,
+                column,
+                opener_column,
+            } => (&expected.closer, &found.closer, column, opener_column),
+            line_type => panic!("Expected a corrupted line but found {:?}", line_type),
+        }
+    }
+
     #[test]
     fn test_line_parser_parse_complete() {
-        let mut parser = LineParser::new(Vec::new());
-        assert!(matches!(
-            parser.parse("([({})]){}<>[{()}]".chars()),
-            LineType::Complete
-        ));
+        let table = DelimiterTable::default();
+        let mut parser = LineParser::new(Vec::new(), &table);
+        assert_eq!(parser.parse("([({})]){}<>[{()}]"), LineType::Complete);
     }
 
     #[test]
     fn test_line_parser_parse_incomplete() {
-        let mut parser = LineParser::new(Vec::new());
-        assert!(matches!(
-            parser.parse("({})[({})][".chars()),
-            LineType::Incomplete(vec) if vec == vec![TokenType::SquareBracket]
-        ));
-        assert!(matches!(
-            parser.parse("({})[({})](".chars()),
-            LineType::Incomplete(vec) if vec == vec![TokenType::Parenthesis]
-        ));
-        assert!(matches!(
-            parser.parse("({})[({})]<".chars()),
-            LineType::Incomplete(vec) if vec == vec![TokenType::AngularBracket]
-        ));
-        assert!(matches!(
-            parser.parse("({})[({})]{".chars()),
-            LineType::Incomplete(vec) if vec == vec![TokenType::Brace]
-        ));
+        let table = DelimiterTable::default();
+        let mut parser = LineParser::new(Vec::new(), &table);
+        assert_eq!(unclosed(parser.parse("({})[({})][")), vec!["]"]);
+        assert_eq!(unclosed(parser.parse("({})[({})](")), vec![")"]);
+        assert_eq!(unclosed(parser.parse("({})[({})]<")), vec![">"]);
+        assert_eq!(unclosed(parser.parse("({})[({})]{")), vec!["}"]);
     }
 
     #[test]
     fn test_line_parser_parse_incomplete_multi_char() {
-        let mut parser = LineParser::new(Vec::new());
-        assert!(matches!(parser.parse("({[]})<[]>{[[<".chars()), 
-            LineType::Incomplete(vec) if vec == vec![TokenType::AngularBracket, TokenType::SquareBracket, TokenType::SquareBracket, TokenType::Brace]));
+        let table = DelimiterTable::default();
+        let mut parser = LineParser::new(Vec::new(), &table);
+        assert_eq!(
+            unclosed(parser.parse("({[]})<[]>{[[<")),
+            vec![">", "]", "]", "}"]
+        );
     }
 
     #[test]
     fn test_line_parser_parse_corrupted() {
-        let mut parser = LineParser::new(Vec::new());
-        assert!(matches!(
-            parser.parse("[({})]<{[)}>".chars()),
-            LineType::Corrupted {
-                expected: TokenType::SquareBracket,
-                found: TokenType::Parenthesis
+        let table = DelimiterTable::default();
+        let mut parser = LineParser::new(Vec::new(), &table);
+        assert_eq!(corrupted(parser.parse("[({})]<{[)}>")), ("]", ")", 10, 9));
+        assert_eq!(
+            corrupted(parser.parse("[({})]{{>}<{[]}>")),
+            ("}", ">", 9, 8)
+        );
+        assert_eq!(corrupted(parser.parse("[({}}]<{[]}>")), (")", "}", 5, 2));
+    }
+
+    #[test]
+    fn test_line_parser_parse_incomplete_columns() {
+        let table = DelimiterTable::default();
+        let mut parser = LineParser::new(Vec::new(), &table);
+        let pairs = table.pairs();
+        assert_eq!(
+            parser.parse("<[]{("),
+            LineType::Incomplete(vec![
+                Opener {
+                    pair: &pairs[0],
+                    column: 5
+                },
+                Opener {
+                    pair: &pairs[2],
+                    column: 4
+                },
+                Opener {
+                    pair: &pairs[3],
+                    column: 1
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_line_parser_parse_unmatched_closer() {
+        let table = DelimiterTable::default();
+        let mut parser = LineParser::new(Vec::new(), &table);
+        let pairs = table.pairs();
+        assert_eq!(
+            parser.parse("()[]}"),
+            LineType::UnmatchedCloser {
+                found: &pairs[2],
+                column: 5
             }
-        ));
-        assert!(matches!(
-            parser.parse("[({})]{{>}<{[]}>".chars()),
-            LineType::Corrupted {
-                expected: TokenType::Brace,
-                found: TokenType::AngularBracket
+        );
+        assert_eq!(
+            parser.parse(">"),
+            LineType::UnmatchedCloser {
+                found: &pairs[3],
+                column: 1
             }
-        ));
-        assert!(matches!(
-            parser.parse("[({}}]<{[]}>".chars()),
-            LineType::Corrupted {
-                expected: TokenType::Parenthesis,
-                found: TokenType::Brace
+        );
+    }
+
+    #[test]
+    fn test_line_parser_parse_unknown_character() {
+        let table = DelimiterTable::default();
+        let mut parser = LineParser::new(Vec::new(), &table);
+        assert_eq!(
+            parser.parse("([a])"),
+            LineType::UnknownCharacter {
+                found: 'a',
+                column: 3
             }
-        ));
+        );
+    }
+
+    #[test]
+    fn test_line_parser_parse_custom_table() {
+        let table = DelimiterTable::new()
+            .with_pair(DelimiterPair::new("/*", "*/", 1, 1))
+            .unwrap()
+            .with_pair(DelimiterPair::new("{{", "}}", 2, 2))
+            .unwrap()
+            .with_pair(DelimiterPair::new("\"", "\"", 3, 3))
+            .unwrap()
+            .skipping_other_characters(true);
+        let mut parser = LineParser::new(Vec::new(), &table);
+        assert_eq!(
+            parser.parse("/* {{ \"name\" }} */ é {{ x }}"),
+            LineType::Complete
+        );
+        assert_eq!(
+            corrupted(parser.parse("é {{ \"a */ }}")),
+            ("\"", "*/", 9, 6)
+        );
+        assert_eq!(unclosed(parser.parse("/* {{ \"a\" ")), vec!["}}", "*/"]);
     }
 }
@@ -1,18 +1,119 @@
-use super::token_type::TokenType;
+use {
+    super::{delimiter_pair::DelimiterPair, opener::Opener},
+    std::fmt::{Display, Formatter, Result},
+};
 
 /// Represents the type of line after parsing. A line can either be:
 /// Complete => The line is formatted correctly
-/// Incomplete => The line is missing closing characters
-/// Corrupted => The line has an incorrect closing character for an opening character
+/// Incomplete => The line is missing closing delimiters
+/// Corrupted => The line has an incorrect closing delimiter for an opening delimiter
+/// UnmatchedCloser => The line has a closing delimiter when nothing is open
+/// UnknownCharacter => The line has a character which isn't part of any delimiter
 ///
-/// If the line is incomplete then it will contain the next expected closing TokenType
-/// And if it's corrupted will contain both the expected closing TokenType, and the invalid TokenType
+/// If the line is incomplete then it will contain the openers that still need closing, innermost first.
+/// Otherwise it will contain the (1 based) column of the first invalid character, and for a corrupted
+/// line also the opener it should have closed. The pairs are the ones in the parser's delimiter table.
 #[derive(Debug, PartialEq)]
-pub enum LineType {
+pub enum LineType<'a> {
     Complete,
-    Incomplete(Vec<TokenType>),
+    Incomplete(Vec<Opener<'a>>),
     Corrupted {
-        expected: TokenType,
-        found: TokenType,
+        expected: &'a DelimiterPair,
+        found: &'a DelimiterPair,
+        column: usize,
+        opener_column: usize,
     },
+    UnmatchedCloser {
+        found: &'a DelimiterPair,
+        column: usize,
+    },
+    UnknownCharacter {
+        found: char,
+        column: usize,
+    },
+}
+
+impl Display for LineType<'_> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Complete => write!(f, "complete"),
+            Self::Incomplete(openers) => {
+                write!(f, "incomplete, expected")?;
+                openers.iter().try_for_each(|opener| {
+                    write!(f, " {} (for column {})", opener.pair.closer, opener.column)
+                })
+            }
+            Self::Corrupted {
+                expected,
+                found,
+                column,
+                opener_column,
+            } => write!(
+                f,
+                "column {}: expected {} to close {} at column {}, found {}",
+                column, expected.closer, expected.opener, opener_column, found.closer
+            ),
+            Self::UnmatchedCloser { found, column } => write!(
+                f,
+                "column {}: found {} with nothing open",
+                column, found.closer
+            ),
+            Self::UnknownCharacter { found, column } => {
+                write!(f, "column {}: unknown character {:?}", column, found)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let braces = DelimiterPair::new("{", "}", 1197, 3);
+        let comment = DelimiterPair::new("/*", "*/", 1, 1);
+        let square_brackets = DelimiterPair::new("[", "]", 57, 2);
+        assert_eq!(LineType::Complete.to_string(), "complete");
+        assert_eq!(
+            LineType::Incomplete(vec![
+                Opener {
+                    pair: &braces,
+                    column: 4
+                },
+                Opener {
+                    pair: &comment,
+                    column: 1
+                }
+            ])
+            .to_string(),
+            "incomplete, expected } (for column 4) */ (for column 1)"
+        );
+        assert_eq!(
+            LineType::Corrupted {
+                expected: &square_brackets,
+                found: &comment,
+                column: 5,
+                opener_column: 2
+            }
+            .to_string(),
+            "column 5: expected ] to close [ at column 2, found */"
+        );
+        assert_eq!(
+            LineType::UnmatchedCloser {
+                found: &braces,
+                column: 1
+            }
+            .to_string(),
+            "column 1: found } with nothing open"
+        );
+        assert_eq!(
+            LineType::UnknownCharacter {
+                found: 'x',
+                column: 4
+            }
+            .to_string(),
+            "column 4: unknown character 'x'"
+        );
+    }
 }
@@ -1,73 +1,76 @@
+mod build_delimiter_table_error;
+mod delimiter_pair;
+mod delimiter_table;
+mod edit;
 mod line_parser;
 mod line_type;
+mod opener;
 mod opening_type;
+mod repair;
 mod token;
-mod token_type;
 
-use {line_parser::LineParser, line_type::LineType, token_type::TokenType};
+pub use {
+    build_delimiter_table_error::BuildDelimiterTableError, delimiter_pair::DelimiterPair,
+    delimiter_table::DelimiterTable, edit::Edit, line_parser::LineParser, line_type::LineType,
+    opener::Opener, repair::Repair,
+};
 
-pub fn run(input: &str) -> (u32, u64) {
-    let (part_1, part_2) = calculate_scores(input);
+pub fn run(input: &str) -> (u64, u64) {
+    let (part_1, part_2) = calculate_scores(input, &DelimiterTable::default());
     (part_1, part_2)
 }
 
-fn calculate_scores(input: &str) -> (u32, u64) {
-    let (part_1, mut autocomplete_scores) = parse_lines(input.trim().lines()).filter(|elem| !matches!(elem, LineType::Complete)).fold((0, vec![]), |(mut corrupted_sum, mut autocomplete_scores), elem| {
+/// Adds up the scores of the corrupted lines and finds the middle score of the incomplete
+/// lines, using the scores given by the delimiter table.
+pub fn calculate_scores(input: &str, table: &DelimiterTable) -> (u64, u64) {
+    let (part_1, mut autocomplete_scores) = parse_lines(input.trim().lines(), table).filter(|elem| !matches!(elem, LineType::Complete)).fold((0, vec![]), |(mut corrupted_sum, mut autocomplete_scores), elem| {
         match elem {
             LineType::Corrupted {
                 found,
                 ..
             } => {
-                corrupted_sum += score_token_type_corrupted(found);
+                corrupted_sum += found.corrupted_score;
+            },
+            // A closer with nothing open is the first illegal character, the same as a corrupted line.
+            LineType::UnmatchedCloser {
+                found,
+                ..
+            } => {
+                corrupted_sum += found.corrupted_score;
             },
             LineType::Incomplete(vec) => {
-                autocomplete_scores.push(calculate_total_autocomplete_score(vec.into_iter()));
+                autocomplete_scores.push(calculate_total_autocomplete_score(vec.into_iter().map(|opener| opener.pair)));
             },
-            _ => panic!("This should have been filtered out. We're only interested in corrupted or incomplete lines.")
+            LineType::UnknownCharacter { .. } => (),
+            LineType::Complete => panic!("This should have been filtered out. We're only interested in corrupted or incomplete lines.")
         }
         (corrupted_sum, autocomplete_scores)
     });
     autocomplete_scores.sort();
-    let part_2 = autocomplete_scores[autocomplete_scores.len() / 2];
+    let part_2 = autocomplete_scores
+        .get(autocomplete_scores.len() / 2)
+        .copied()
+        .unwrap_or(0);
     (part_1, part_2)
 }
 
 fn parse_lines<'a>(
     lines: impl Iterator<Item = &'a str> + 'a,
-) -> impl Iterator<Item = LineType> + 'a {
-    let mut parser = LineParser::new(Vec::new());
+    table: &'a DelimiterTable,
+) -> impl Iterator<Item = LineType<'a>> + 'a {
+    let mut parser = LineParser::new(Vec::new(), table);
     lines.filter_map(move |line| {
         let line = line.trim();
         if line.is_empty() {
             None
         } else {
-            Some(parser.parse(line.chars()))
+            Some(parser.parse(line))
         }
     })
 }
 
-fn score_token_type_corrupted(token_type: TokenType) -> u32 {
-    match token_type {
-        TokenType::Parenthesis => 3,
-        TokenType::SquareBracket => 57,
-        TokenType::Brace => 1197,
-        TokenType::AngularBracket => 25137,
-    }
-}
-
-fn score_token_type_incomplete(token_type: TokenType) -> u64 {
-    match token_type {
-        TokenType::Parenthesis => 1,
-        TokenType::SquareBracket => 2,
-        TokenType::Brace => 3,
-        TokenType::AngularBracket => 4,
-    }
-}
-
-fn calculate_total_autocomplete_score(input: impl Iterator<Item = TokenType>) -> u64 {
-    input.fold(0, |state, elem| {
-        state * 5 + score_token_type_incomplete(elem)
-    })
+fn calculate_total_autocomplete_score<'a>(input: impl Iterator<Item = &'a DelimiterPair>) -> u64 {
+    input.fold(0, |state, elem| state * 5 + elem.incomplete_score)
 }
 
 #[cfg(test)]
@@ -83,15 +86,31 @@ mod tests {
         (({})){[
         
         "#;
+        let table = DelimiterTable::default();
+        let pairs = table.pairs();
         let expected = vec![
             LineType::Complete,
             LineType::Corrupted {
-                expected: TokenType::SquareBracket,
-                found: TokenType::Brace,
+                expected: &pairs[1],
+                found: &pairs[2],
+                column: 3,
+                opener_column: 2,
             },
-            LineType::Incomplete(vec![TokenType::SquareBracket, TokenType::Brace]),
+            LineType::Incomplete(vec![
+                Opener {
+                    pair: &pairs[1],
+                    column: 8,
+                },
+                Opener {
+                    pair: &pairs[2],
+                    column: 7,
+                },
+            ]),
         ];
-        assert_eq!(parse_lines(INPUT.lines()).collect::<Vec<_>>(), expected);
+        assert_eq!(
+            parse_lines(INPUT.lines(), &table).collect::<Vec<_>>(),
+            expected
+        );
     }
 
     #[test]
@@ -106,30 +125,42 @@ mod tests {
             [<(<(<(<{}))><([]([]()
             <{([([[(<>()){}]>(<<{{
             <{([{{}}[<[[[<>{}]]]>[]]"#;
+        let table = DelimiterTable::default();
+        let pairs = table.pairs();
         let expected = vec![
             LineType::Corrupted {
-                expected: TokenType::SquareBracket,
-                found: TokenType::Brace,
+                expected: &pairs[1],
+                found: &pairs[2],
+                column: 13,
+                opener_column: 8,
             },
             LineType::Corrupted {
-                expected: TokenType::SquareBracket,
-                found: TokenType::Parenthesis,
+                expected: &pairs[1],
+                found: &pairs[0],
+                column: 9,
+                opener_column: 4,
             },
             LineType::Corrupted {
-                expected: TokenType::Parenthesis,
-                found: TokenType::SquareBracket,
+                expected: &pairs[0],
+                found: &pairs[1],
+                column: 8,
+                opener_column: 5,
             },
             LineType::Corrupted {
-                expected: TokenType::AngularBracket,
-                found: TokenType::Parenthesis,
+                expected: &pairs[3],
+                found: &pairs[0],
+                column: 11,
+                opener_column: 8,
             },
             LineType::Corrupted {
-                expected: TokenType::SquareBracket,
-                found: TokenType::AngularBracket,
+                expected: &pairs[1],
+                found: &pairs[3],
+                column: 17,
+                opener_column: 6,
             },
         ];
         assert_eq!(
-            parse_lines(INPUT.lines())
+            parse_lines(INPUT.lines(), &table)
                 .filter(|elem| matches!(elem, LineType::Corrupted { .. }))
                 .collect::<Vec<_>>(),
             expected
@@ -137,19 +168,23 @@ mod tests {
     }
 
     #[test]
-    fn test_score_token_type_corrupted() {
-        assert_eq!(score_token_type_corrupted(TokenType::Parenthesis), 3);
-        assert_eq!(score_token_type_corrupted(TokenType::SquareBracket), 57);
-        assert_eq!(score_token_type_corrupted(TokenType::Brace), 1197);
-        assert_eq!(score_token_type_corrupted(TokenType::AngularBracket), 25137);
-    }
-
-    #[test]
-    fn test_score_token_type_incomplete() {
-        assert_eq!(score_token_type_incomplete(TokenType::Parenthesis), 1);
-        assert_eq!(score_token_type_incomplete(TokenType::SquareBracket), 2);
-        assert_eq!(score_token_type_incomplete(TokenType::Brace), 3);
-        assert_eq!(score_token_type_incomplete(TokenType::AngularBracket), 4);
+    fn test_default_scores() {
+        let table = DelimiterTable::default();
+        let scores = table
+            .pairs()
+            .iter()
+            .map(|pair| {
+                (
+                    pair.closer.as_str(),
+                    pair.corrupted_score,
+                    pair.incomplete_score,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            scores,
+            vec![(")", 3, 1), ("]", 57, 2), ("}", 1197, 3), (">", 25137, 4)]
+        );
     }
 
     #[test]
@@ -164,22 +199,38 @@ mod tests {
             [<(<(<(<{}))><([]([]()
             <{([([[(<>()){}]>(<<{{
             <{([{{}}[<[[[<>{}]]]>[]]"#;
-        const EXPECTED: (u32, u64) = (26397, 288957);
-        assert_eq!(calculate_scores(INPUT), EXPECTED);
+        const EXPECTED: (u64, u64) = (26397, 288957);
+        assert_eq!(
+            calculate_scores(INPUT, &DelimiterTable::default()),
+            EXPECTED
+        );
     }
 
     #[test]
     fn test_calculate_autocomplete_score() {
-        const INPUT: &[TokenType] = &[
-            TokenType::SquareBracket,
-            TokenType::Parenthesis,
-            TokenType::Brace,
-            TokenType::AngularBracket,
-        ];
+        let table = DelimiterTable::default();
+        let pairs = table.pairs();
+        let input = [&pairs[1], &pairs[0], &pairs[2], &pairs[3]];
         const EXPECTED: u64 = 294;
         assert_eq!(
-            calculate_total_autocomplete_score(INPUT.into_iter().cloned()),
+            calculate_total_autocomplete_score(input.into_iter()),
             EXPECTED
         );
     }
+
+    #[test]
+    fn test_calculate_scores_custom_table() {
+        let table = DelimiterTable::new()
+            .with_pair(DelimiterPair::new("/*", "*/", 100, 1))
+            .unwrap()
+            .with_pair(DelimiterPair::new("{{", "}}", 10, 2))
+            .unwrap()
+            .skipping_other_characters(true);
+        const INPUT: &str = r#"
+            /* header */ {{ title }}
+            {{ name */
+            /* {{ list
+            {{ item }} }}"#;
+        assert_eq!(calculate_scores(INPUT, &table), (110, 11));
+    }
 }
@@ -0,0 +1,9 @@
+use super::delimiter_pair::DelimiterPair;
+
+/// An opening delimiter which hasn't been closed yet, along with the (1 based) column that it
+/// was found in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Opener<'a> {
+    pub pair: &'a DelimiterPair,
+    pub column: usize,
+}
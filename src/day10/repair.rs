@@ -0,0 +1,530 @@
+use {
+    super::{
+        delimiter_pair::DelimiterPair, delimiter_table::DelimiterTable, edit::Edit,
+        opening_type::OpeningType,
+    },
+    std::{collections::HashMap, iter, ptr},
+};
+
+/// The most ranges of left over tokens the fewest edits are worked out for before the exact
+/// repair gives up, which is about as many as there are in 360 tokens.
+const MAX_EXACT_RANGES: usize = 1 << 16;
+
+/// The edits which turn a line into a complete one, listed in the order of their columns.
+///
+/// Delimiters which already match next to each other (once any matched pairs between them are
+/// removed) never need editing, so they're removed first with a stack in linear time. The
+/// fewest edits for what's left over are then found exactly, but only for the ranges of it
+/// which could be part of a repair no worse than a linear greedy one, as every edit changes
+/// how many more openers than closers there are by at most two. This is usually only a small
+/// part of a long line, but if there are too many ranges the greedy repair is used instead,
+/// which still completes the line but might not use the fewest edits, and is_minimal is false.
+#[derive(Debug, PartialEq)]
+pub struct Repair {
+    pub edits: Vec<Edit>,
+    pub is_minimal: bool,
+}
+
+impl Repair {
+    pub fn new(line: &str, table: &DelimiterTable) -> Self {
+        let residue = reduce(line, table);
+        let end_column = line.chars().count() + 1;
+        let greedy = greedy_repair(&residue, end_column);
+        // Symmetric delimiters can go either way, so they don't give a bound on the ranges.
+        let band = if residue.iter().any(Item::is_symmetric) {
+            residue.len()
+        } else {
+            2 * greedy.len()
+        };
+        match ExactRepair::new(&residue, table, end_column, band) {
+            Some(repair) => Self {
+                edits: repair.edits(),
+                is_minimal: true,
+            },
+            None => Self {
+                edits: greedy,
+                is_minimal: false,
+            },
+        }
+    }
+
+    /// Applies the edits to the line they were made for.
+    pub fn apply(&self, line: &str) -> String {
+        let chars = line.chars().collect::<Vec<_>>();
+        let mut repaired = String::with_capacity(line.len());
+        let mut next = 0;
+        self.edits.iter().for_each(|edit| {
+            let (column, skip, text) = match edit {
+                Edit::Insert { column, text } => (*column, 0, text.as_str()),
+                Edit::Delete { column, text } => (*column, text.chars().count(), ""),
+                Edit::Substitute {
+                    column,
+                    found,
+                    replacement,
+                } => (*column, found.chars().count(), replacement.as_str()),
+            };
+            repaired.extend(&chars[next..column - 1]);
+            repaired.push_str(text);
+            next = column - 1 + skip;
+        });
+        repaired.extend(&chars[next..]);
+        repaired
+    }
+}
+
+// A token which is left over after removing matched pairs, which is either a delimiter or
+// some other character when the table doesn't skip them.
+struct Item<'a, 't> {
+    delimiter: Option<(&'t DelimiterPair, OpeningType)>,
+    column: usize,
+    text: &'a str,
+}
+
+impl Item<'_, '_> {
+    fn can_open(&self, pair: &DelimiterPair) -> bool {
+        matches!(self.delimiter, Some((other, opening_type))
+            if ptr::eq(other, pair) && (opening_type == OpeningType::Opening || pair.is_symmetric()))
+    }
+
+    fn can_close(&self, pair: &DelimiterPair) -> bool {
+        matches!(self.delimiter, Some((other, opening_type))
+            if ptr::eq(other, pair) && (opening_type == OpeningType::Closing || pair.is_symmetric()))
+    }
+
+    fn opens(&self) -> Option<&DelimiterPair> {
+        self.delimiter
+            .map(|(pair, _)| pair)
+            .filter(|pair| self.can_open(pair))
+    }
+
+    fn closes(&self) -> Option<&DelimiterPair> {
+        self.delimiter
+            .map(|(pair, _)| pair)
+            .filter(|pair| self.can_close(pair))
+    }
+
+    fn is_symmetric(&self) -> bool {
+        self.delimiter.is_some_and(|(pair, _)| pair.is_symmetric())
+    }
+
+    // How many more openers than closers the token adds.
+    fn balance(&self) -> isize {
+        match self.delimiter {
+            Some((_, OpeningType::Opening)) => 1,
+            Some((_, OpeningType::Closing)) => -1,
+            None => 0,
+        }
+    }
+}
+
+// Splits the line into tokens, removing each opener that's directly followed by its closer
+// once the pairs between them have been removed, and gives back what's left in order.
+fn reduce<'a, 't>(line: &'a str, table: &'t DelimiterTable) -> Vec<Item<'a, 't>> {
+    let mut residue: Vec<Item> = vec![];
+    let (mut rest, mut column) = (line, 1);
+    while let Some(c) = rest.chars().next() {
+        let innermost = residue
+            .last()
+            .and_then(|item| item.delimiter)
+            .filter(|(_, opening_type)| *opening_type == OpeningType::Opening)
+            .map(|(pair, _)| pair);
+        let (delimiter, length) = match table.match_token(rest, innermost) {
+            Some(token) => (Some((token.pair, token.opening_type)), token.length),
+            None => (None, c.len_utf8()),
+        };
+        let item = Item {
+            delimiter,
+            column,
+            text: &rest[..length],
+        };
+        match item.delimiter {
+            Some((pair, OpeningType::Closing))
+                if innermost.is_some_and(|innermost| ptr::eq(innermost, pair)) =>
+            {
+                residue.pop();
+            }
+            None if table.skips_other_characters() => (),
+            _ => residue.push(item),
+        }
+        column += rest[..length].chars().count();
+        rest = &rest[length..];
+    }
+    residue
+}
+
+// Finds the fewest edits for the left over tokens by working out the fewest for every range of
+// them, where the first token in a range is either paired with a later token (editing either
+// if they don't match) or edited on its own (inserting a closer for an opener, otherwise
+// deleting it). Ranges where the openers and closers differ by more than the band can't be
+// part of the repair, so they're skipped.
+struct ExactRepair<'r, 'a, 't> {
+    residue: &'r [Item<'a, 't>],
+    table: &'t DelimiterTable,
+    end_column: usize,
+    // The starts of the ranges within the band which end at each index, in order.
+    starts: Vec<Vec<usize>>,
+    costs: HashMap<(usize, usize), usize>,
+}
+
+// A step in listing the edits, which is either to list the edits for a range or to add an
+// edit which comes after the ranges before it.
+enum Step {
+    Range(usize, usize),
+    Edit(Edit),
+}
+
+impl<'r, 'a, 't> ExactRepair<'r, 'a, 't> {
+    // Works out the fewest edits for each range, giving None if there are too many ranges.
+    fn new(
+        residue: &'r [Item<'a, 't>],
+        table: &'t DelimiterTable,
+        end_column: usize,
+        band: usize,
+    ) -> Option<Self> {
+        let balances = iter::once(0)
+            .chain(residue.iter().scan(0, |balance, item| {
+                *balance += item.balance();
+                Some(*balance)
+            }))
+            .collect::<Vec<_>>();
+        let min = *balances.iter().min().unwrap();
+        let max = *balances.iter().max().unwrap();
+        let band = band.min(residue.len()) as isize;
+
+        // The balances move by at most one per token, so every balance between the lowest and
+        // highest so far has been seen, and each one looked at adds at least one range.
+        let mut indices = vec![vec![]; (max - min + 1) as usize];
+        let (mut low, mut high) = (0, 0);
+        let mut starts = Vec::with_capacity(balances.len());
+        let mut ranges = 0;
+        for (end, balance) in balances.iter().enumerate() {
+            indices[(balance - min) as usize].push(end);
+            (low, high) = (low.min(*balance), high.max(*balance));
+            let mut range_starts = ((balance - band).max(low)..=(balance + band).min(high))
+                .flat_map(|balance| indices[(balance - min) as usize].iter().copied())
+                .collect::<Vec<_>>();
+            ranges += range_starts.len();
+            if ranges > MAX_EXACT_RANGES {
+                return None;
+            }
+            range_starts.sort_unstable();
+            starts.push(range_starts);
+        }
+
+        let mut repair = Self {
+            residue,
+            table,
+            end_column,
+            starts,
+            costs: HashMap::with_capacity(ranges),
+        };
+        (1..=residue.len()).for_each(|end| {
+            // The last start is the end itself, for the empty range.
+            (0..repair.starts[end].len() - 1).rev().for_each(|idx| {
+                let start = repair.starts[end][idx];
+                let cost = repair
+                    .others(start, end)
+                    .filter_map(|other| repair.paired_cost(start, other, end))
+                    .chain(repair.cost(start + 1, end).map(|cost| cost + 1))
+                    .min();
+                if let Some(cost) = cost {
+                    repair.costs.insert((start, end), cost);
+                }
+            })
+        });
+        Some(repair)
+    }
+
+    // The fewest edits for the range, or None if it's outside the band.
+    fn cost(&self, start: usize, end: usize) -> Option<usize> {
+        match start == end {
+            true => Some(0),
+            false => self.costs.get(&(start, end)).copied(),
+        }
+    }
+
+    // The tokens which the first token of the range could be paired with, leaving a range
+    // within the band after them.
+    fn others(&self, start: usize, end: usize) -> impl Iterator<Item = usize> + Clone + '_ {
+        let starts = &self.starts[end];
+        starts[starts.partition_point(|other_start| *other_start < start + 2)..]
+            .iter()
+            .map(|other_start| other_start - 1)
+    }
+
+    // The pair that two tokens should be made into, keeping whichever of them already fits.
+    fn pair_for(&self, start: usize, other: usize) -> Option<&'t DelimiterPair> {
+        let (first, second) = (&self.residue[start], &self.residue[other]);
+        first
+            .delimiter
+            .filter(|(pair, _)| first.can_open(pair))
+            .or(second.delimiter.filter(|(pair, _)| second.can_close(pair)))
+            .map(|(pair, _)| pair)
+            .or(self.table.pairs().first())
+    }
+
+    fn paired_cost(&self, start: usize, other: usize, end: usize) -> Option<usize> {
+        let pair = self.pair_for(start, other)?;
+        Some(
+            !self.residue[start].can_open(pair) as usize
+                + !self.residue[other].can_close(pair) as usize
+                + self.cost(start + 1, other)?
+                + self.cost(other + 1, end)?,
+        )
+    }
+
+    // Lists the edits in column order by following the choices which gave the fewest edits.
+    // This uses a stack of steps rather than recursing, as the ranges can be nested deeply.
+    fn edits(&self) -> Vec<Edit> {
+        let mut edits = vec![];
+        let mut steps = vec![Step::Range(0, self.residue.len())];
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Range(start, end) if start < end => self.add_steps(start, end, &mut steps),
+                Step::Range(..) => (),
+                Step::Edit(edit) => edits.push(edit),
+            }
+        }
+        edits
+    }
+
+    // Adds the steps for the range, in reverse order as they're taken from the end.
+    fn add_steps(&self, start: usize, end: usize, steps: &mut Vec<Step>) {
+        let cost = self.cost(start, end);
+        let first = &self.residue[start];
+        // Prefer pairing with a token that already matches, then editing the first token on
+        // its own, and only then substituting to make a pair, so that lines which are only
+        // missing closers are completed at the end.
+        let mut paired = self
+            .others(start, end)
+            .filter(|other| self.paired_cost(start, *other, end) == cost);
+        let matching = paired.clone().find(|other| {
+            self.pair_for(start, *other)
+                .is_some_and(|pair| first.can_open(pair) && self.residue[*other].can_close(pair))
+        });
+        let paired = matching.or_else(|| {
+            (self.cost(start + 1, end).map(|cost| cost + 1) != cost)
+                .then(|| paired.next())
+                .flatten()
+        });
+        match (paired, first.opens()) {
+            (Some(other), _) => {
+                let pair = self.pair_for(start, other).unwrap();
+                let second = &self.residue[other];
+                steps.push(Step::Range(other + 1, end));
+                if !second.can_close(pair) {
+                    steps.push(Step::Edit(substitute(second, &pair.closer)));
+                }
+                steps.push(Step::Range(start + 1, other));
+                if !first.can_open(pair) {
+                    steps.push(Step::Edit(substitute(first, &pair.opener)));
+                }
+            }
+            (None, Some(pair)) => {
+                let column = self
+                    .residue
+                    .get(end)
+                    .map_or(self.end_column, |item| item.column);
+                steps.push(Step::Edit(Edit::Insert {
+                    column,
+                    text: pair.closer.clone(),
+                }));
+                steps.push(Step::Range(start + 1, end));
+            }
+            (None, None) => {
+                steps.push(Step::Range(start + 1, end));
+                steps.push(Step::Edit(delete(first)));
+            }
+        }
+    }
+}
+
+// Repairs the left over tokens in one pass with a stack. An opener is pushed, and a closer
+// which doesn't match the innermost opener either closes the opener under it (inserting a
+// closer for the innermost one), is substituted for the innermost opener's closer, or is
+// deleted if nothing is open. Anything still open at the end is closed.
+fn greedy_repair(residue: &[Item], end_column: usize) -> Vec<Edit> {
+    let mut edits = vec![];
+    let mut stack: Vec<&DelimiterPair> = vec![];
+    residue.iter().for_each(|item| {
+        let innermost = stack.last().copied();
+        if let Some(pair) = item.opens().filter(|pair| {
+            !(pair.is_symmetric() && innermost.is_some_and(|innermost| ptr::eq(innermost, *pair)))
+        }) {
+            stack.push(pair);
+            return;
+        }
+        match (item.closes(), innermost) {
+            (_, None) => {
+                edits.push(delete(item));
+                return;
+            }
+            (Some(pair), Some(innermost)) if ptr::eq(pair, innermost) => (),
+            (Some(pair), Some(innermost))
+                if stack.len() >= 2 && ptr::eq(stack[stack.len() - 2], pair) =>
+            {
+                edits.push(Edit::Insert {
+                    column: item.column,
+                    text: innermost.closer.clone(),
+                });
+                stack.pop();
+            }
+            (_, Some(innermost)) => edits.push(substitute(item, &innermost.closer)),
+        }
+        stack.pop();
+    });
+    while let Some(pair) = stack.pop() {
+        edits.push(Edit::Insert {
+            column: end_column,
+            text: pair.closer.clone(),
+        });
+    }
+    edits
+}
+
+fn substitute(item: &Item, replacement: &str) -> Edit {
+    Edit::Substitute {
+        column: item.column,
+        found: item.text.to_string(),
+        replacement: replacement.to_string(),
+    }
+}
+
+fn delete(item: &Item) -> Edit {
+    Edit::Delete {
+        column: item.column,
+        text: item.text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::day10::{line_parser::LineParser, line_type::LineType},
+    };
+
+    // Checks the repair completes the line, returning how many edits it took.
+    fn repair(line: &str, table: &DelimiterTable) -> (usize, bool) {
+        let repair = Repair::new(line, table);
+        let repaired = repair.apply(line);
+        assert_eq!(
+            LineParser::new(vec![], table).parse(&repaired),
+            LineType::Complete,
+            "{} was repaired to {}",
+            line,
+            repaired
+        );
+        (repair.edits.len(), repair.is_minimal)
+    }
+
+    #[test]
+    fn test_repair_complete() {
+        assert_eq!(
+            Repair::new("([({})]){}<>", &DelimiterTable::default()),
+            Repair {
+                edits: vec![],
+                is_minimal: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_repair_edits() {
+        let table = DelimiterTable::default();
+        assert_eq!(
+            Repair::new("{()]", &table).edits,
+            vec![Edit::Substitute {
+                column: 4,
+                found: "]".to_string(),
+                replacement: "}".to_string()
+            }]
+        );
+        assert_eq!(
+            Repair::new("(<>]))", &table).edits,
+            vec![Edit::Substitute {
+                column: 4,
+                found: "]".to_string(),
+                replacement: "(".to_string()
+            }]
+        );
+        assert_eq!(
+            Repair::new("[<>", &table).edits,
+            vec![Edit::Insert {
+                column: 4,
+                text: "]".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_repair_example() {
+        const INPUT: &str = r#"[({(<(())[]>[[{[]{<()<>>
+            [(()[<>])]({[<{<<[]>>(
+            {([(<{}[<>[]}>{[]{[(<()>
+            (((({<>}<{<{<>}{[]{[]{}
+            [[<[([]))<([[{}[[()]]]
+            [{[{({}]{}}([{[{{{}}([]
+            {<[[]]>}<{[{[{[]{()[[[]
+            [<(<(<(<{}))><([]([]()
+            <{([([[(<>()){}]>(<<{{
+            <{([{{}}[<[[[<>{}]]]>[]]"#;
+        let table = DelimiterTable::default();
+        let edit_counts = INPUT
+            .lines()
+            .map(|line| repair(line.trim(), &table))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            edit_counts,
+            [4, 3, 5, 5, 4, 5, 5, 5, 6, 2].map(|count| (count, true))
+        );
+    }
+
+    #[test]
+    fn test_repair_small() {
+        let table = DelimiterTable::default();
+        assert_eq!(repair("(]", &table), (1, true));
+        assert_eq!(repair("((]))", &table), (1, true));
+        assert_eq!(repair("((", &table), (1, true));
+        assert_eq!(repair("}{", &table), (2, true));
+        assert_eq!(repair(")(", &table), (2, true));
+    }
+
+    #[test]
+    fn test_repair_custom_table() {
+        let table = DelimiterTable::new()
+            .with_pair(DelimiterPair::new("/*", "*/", 1, 1))
+            .unwrap()
+            .with_pair(DelimiterPair::new("\"", "\"", 1, 1))
+            .unwrap()
+            .skipping_other_characters(true);
+        let line = "/* \"é */";
+        let repair = Repair::new(line, &table);
+        assert_eq!(
+            repair.edits,
+            vec![Edit::Insert {
+                column: 7,
+                text: "\"".to_string()
+            }]
+        );
+        assert_eq!(repair.apply(line), "/* \"é \"*/");
+    }
+
+    #[test]
+    fn test_repair_unknown_characters() {
+        let table = DelimiterTable::default();
+        assert_eq!(repair("(a]", &table), (2, true));
+    }
+
+    #[test]
+    fn test_repair_long_line() {
+        let table = DelimiterTable::default();
+        // Only "<(]" is left after removing the matched pairs, so it's still exact.
+        let line = format!("{}{}]", "<(".repeat(1000), ")>".repeat(999));
+        assert_eq!(repair(&line, &table), (2, true));
+        let line = "{)".repeat(1000);
+        assert_eq!(repair(&line, &table), (1000, false));
+        let line = format!("{}]{}", "(".repeat(1000), ")".repeat(1000));
+        assert_eq!(repair(&line, &table), (1, true));
+    }
+}
@@ -0,0 +1,39 @@
+/// What happens to neighbours which would be off the edge of the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EdgeBehaviour {
+    /// Cells off the edge don't exist, which is what the puzzle uses.
+    #[default]
+    Bounded,
+    /// The grid wraps around, so cells off one edge are the cells on the opposite edge.
+    Wrapping,
+}
+
+impl EdgeBehaviour {
+    /// Moves the coordinate by the offset along an axis of the given length, giving None if
+    /// it ends up off the edge of a bounded grid.
+    pub fn apply(&self, coordinate: usize, offset: isize, length: usize) -> Option<usize> {
+        match self {
+            Self::Bounded => coordinate
+                .checked_add_signed(offset)
+                .filter(|coordinate| *coordinate < length),
+            Self::Wrapping => {
+                Some((coordinate as isize + offset).rem_euclid(length as isize) as usize)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(EdgeBehaviour::Bounded.apply(0, -1, 5), None);
+        assert_eq!(EdgeBehaviour::Bounded.apply(4, 1, 5), None);
+        assert_eq!(EdgeBehaviour::Bounded.apply(2, 1, 5), Some(3));
+        assert_eq!(EdgeBehaviour::Wrapping.apply(0, -1, 5), Some(4));
+        assert_eq!(EdgeBehaviour::Wrapping.apply(4, 1, 5), Some(0));
+        assert_eq!(EdgeBehaviour::Wrapping.apply(2, -1, 5), Some(1));
+    }
+}
@@ -1,8 +1,20 @@
-use {crate::common::collections::Stack, std::borrow::Borrow};
+use {
+    super::{EdgeBehaviour, GridConfig, Neighbourhood},
+    crate::common::{
+        collections::Stack,
+        cycles::{find_cycle, Cycle},
+        netpbm::Graymap,
+    },
+    std::borrow::Borrow,
+};
+
+/// The most steps to run looking for the energy levels to repeat before giving up.
+const MAX_CYCLE_STEPS: usize = 1_000_000;
 
 /// Represents the grid of octopuses and allows us to have a strong type
 /// that can only be constructed from valid input. If we have an instance
 /// of this type then we know that it's a grid and all lines are equal length.
+#[derive(Clone)]
 pub struct Grid<S> {
     grid: Vec<u8>,
     flash_stack: S,
@@ -10,23 +22,104 @@ pub struct Grid<S> {
     height: usize,
     flash_count: u32,
     step_count: u32,
+    config: GridConfig,
+    flashed: Vec<(usize, usize)>,
 }
 
 impl<S: Stack<(usize, usize)>> Grid<S> {
+    /// Changes how the octopuses flash. Panics if the threshold is 0 or any energy level is
+    /// already at the threshold, or if a wrapping hex grid has an odd height, as the shifted
+    /// rows wouldn't line up when wrapping from the bottom to the top.
+    pub fn with_config(mut self, config: GridConfig) -> Self {
+        assert!(
+            config.threshold > 0,
+            "The flash threshold must be at least 1."
+        );
+        assert!(
+            self.grid.iter().all(|elem| *elem < config.threshold),
+            "Energy levels must be below the flash threshold of {}.",
+            config.threshold
+        );
+        assert!(
+            !(config.neighbourhood == Neighbourhood::Hex
+                && config.edge_behaviour == EdgeBehaviour::Wrapping
+                && self.height % 2 == 1),
+            "A wrapping hex grid must have an even height."
+        );
+        self.config = config;
+        self
+    }
+
     /// Runs X iterations of the loop and returns the number of flashes.
     pub fn run_step_count(&mut self, count: u32) -> u32 {
+        self.run_step_count_with(count, |_, _| ())
+    }
+
+    /// Runs X iterations of the loop and returns the number of flashes. After each step the
+    /// observer is given the number of steps run so far (including any before this call) and
+    /// the (x, y) coordinates of the cells which flashed during it, in the order they flashed.
+    pub fn run_step_count_with(
+        &mut self,
+        count: u32,
+        mut observer: impl FnMut(u32, &[(usize, usize)]),
+    ) -> u32 {
         self.flash_count = 0;
-        (0..count).for_each(|_| self.run_single_step());
+        (0..count).for_each(|_| {
+            self.run_single_step();
+            observer(self.step_count, &self.flashed);
+        });
         self.flash_count
     }
 
     /// Runs single steps until the entire grid is synchronized and flashes at the same time.
-    /// Returns the total number of steps that were required to achieve that state.
-    pub fn run_until_synchronized_flash(&mut self) -> u32 {
-        while !self.is_synchronized() {
+    /// Returns the total number of steps that were required to achieve that state, or None
+    /// if the grid never synchronizes (or its energy levels don't repeat within a million
+    /// steps, so there's no way to tell).
+    pub fn run_until_synchronized_flash(&mut self) -> Option<u32>
+    where
+        S: Clone,
+    {
+        // Every state the grid will ever be in comes up within the first start + period steps.
+        let cycle = self.energy_cycle()?;
+        for _ in 0..cycle.start + cycle.period {
+            if self.is_synchronized() {
+                return Some(self.step_count);
+            }
             self.run_single_step();
         }
-        self.step_count
+        None
+    }
+
+    /// Finds the cycle that the energy levels end up in when running steps from the current
+    /// state, giving None if they don't repeat within a million steps.
+    pub fn energy_cycle(&self) -> Option<Cycle<Self>>
+    where
+        S: Clone,
+    {
+        find_cycle(
+            self,
+            Self::run_single_step,
+            |grid| grid.grid.clone(),
+            MAX_CYCLE_STEPS,
+        )
+    }
+
+    /// Counts the flashes there would be over the given number of steps from the current state,
+    /// without running them all. Gives None if the energy levels don't repeat within a million
+    /// steps.
+    pub fn count_flashes_after(&self, steps: usize) -> Option<u128>
+    where
+        S: Clone,
+    {
+        let cycle = self.energy_cycle()?;
+        let mut grid = self.clone();
+        let flashes = (0..cycle.start + cycle.period)
+            .map(|_| {
+                grid.run_single_step();
+                grid.flashed.len() as u64
+            })
+            .collect::<Vec<_>>();
+        Some(cycle.extrapolate_sum(&flashes, steps))
     }
 
     /// Returns true only if all cells are at energy level 0 (they flashed at the same
@@ -35,9 +128,10 @@ impl<S: Stack<(usize, usize)>> Grid<S> {
         self.grid.iter().all(|elem| *elem == 0)
     }
 
-    /// Runs a single time step of the energy increase + flash and
-    /// returns the number of cells that flashed.
+    /// Runs a single time step of the energy increase + flash, keeping track of the cells
+    /// that flashed.
     fn run_single_step(&mut self) {
+        self.flashed.clear();
         self.increase_energy_levels();
         self.process_flash_stack();
         self.reset_flashed_energy_levels();
@@ -45,67 +139,54 @@ impl<S: Stack<(usize, usize)>> Grid<S> {
     }
 
     /// Increases the energy levels in the first step of the process. This
-    /// can't increase energy levels past 10 (10 indicates it should flash).
-    /// Pushes any that are turning 10 onto the flash_stack for flashing.
+    /// can't increase energy levels past the threshold (which indicates it should flash).
+    /// Pushes any that are reaching the threshold onto the flash_stack for flashing.
     fn increase_energy_levels(&mut self) {
-        self.grid.iter_mut().enumerate().for_each(|(idx, elem)| {
-            if *elem < 10 {
-                *elem += 1;
-                if *elem == 10 {
-                    self.flash_stack.push((idx % self.width, idx / self.width));
-                    self.flash_count += 1;
-                }
-            }
-        });
+        (0..self.grid.len()).for_each(|idx| self.try_flash((idx % self.width, idx / self.width)));
     }
 
     /// Processes the flash stack until it's empty. This could run through multiple
     /// iterations to process all the flashes.
     fn process_flash_stack(&mut self) {
+        let GridConfig {
+            neighbourhood,
+            edge_behaviour,
+            ..
+        } = self.config;
         while let Some((x, y)) = self.flash_stack.pop() {
-            // process top row neighbours
-            if y > 0 {
-                if x > 0 {
-                    self.try_flash((x - 1, y - 1));
+            neighbourhood.offsets(y).iter().for_each(|(dx, dy)| {
+                if let (Some(x), Some(y)) = (
+                    edge_behaviour.apply(x, *dx, self.width),
+                    edge_behaviour.apply(y, *dy, self.height),
+                ) {
+                    self.try_flash((x, y));
                 }
-                self.try_flash((x, y - 1));
-                self.try_flash((x + 1, y - 1));
-            }
-
-            // Process same row neighbours
-            if x > 0 {
-                self.try_flash((x - 1, y));
-            }
-            self.try_flash((x + 1, y));
-
-            // Process bottom row neigbours
-            if x > 0 {
-                self.try_flash((x - 1, y + 1));
-            }
-            self.try_flash((x, y + 1));
-            self.try_flash((x + 1, y + 1));
+            });
         }
     }
 
-    /// Resets all the energy level 10 cells back to 0.
+    /// Resets all the cells at the threshold energy level back to 0.
     fn reset_flashed_energy_levels(&mut self) {
+        let threshold = self.config.threshold;
         self.grid.iter_mut().for_each(|elem| {
-            if *elem == 10 {
+            if *elem == threshold {
                 *elem = 0;
             }
         });
     }
 
-    /// Function which increments the element at the given coordinate if it's less than 10, and then
-    /// if it hits 10, pushes it onto the flash stack for processing.
+    /// Function which increments the element at the given coordinate if it's less than the
+    /// threshold, and then if it hits the threshold, pushes it onto the flash stack for processing.
     fn try_flash(&mut self, coord: (usize, usize)) {
         let (x, y) = coord;
+        let threshold = self.config.threshold;
         if x < self.width && y < self.height {
             let idx = y * self.width + x;
-            if self.grid[idx] < 10 {
+            if self.grid[idx] < threshold {
                 self.grid[idx] += 1;
-                if self.grid[idx] == 10 {
+                if self.grid[idx] == threshold {
                     self.flash_stack.push((x, y));
+                    self.flashed.push((x, y));
                     self.flash_count += 1;
                 }
             }
@@ -113,6 +194,25 @@ impl<S: Stack<(usize, usize)>> Grid<S> {
     }
 }
 
+// The energy levels between steps, which are always below the threshold.
+impl<S> Graymap for Grid<S> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn max_value(&self) -> u16 {
+        self.config.threshold as u16 - 1
+    }
+
+    fn level(&self, col: usize, row: usize) -> u16 {
+        self.grid[row * self.width + col] as u16
+    }
+}
+
 /// Makes a grid from an iterator over some type that can be borrowed as a string slice (so it works with owned strings and also
 /// with string slices). Panics if the iterator contains invalid data which could be non-numeric characters, or different sized line
 /// lengths.
@@ -149,6 +249,8 @@ where
             height,
             flash_count: 0,
             step_count: 0,
+            config: GridConfig::default(),
+            flashed: vec![],
         }
     }
 }
@@ -240,6 +342,8 @@ mod tests {
             height,
             flash_count: 0,
             step_count: 0,
+            config: GridConfig::default(),
+            flashed: vec![],
         };
         grid.try_flash((2, 1));
         assert_eq!(grid.grid, vec![1, 2, 3, 4, 5, 10, 7, 8, 9]);
@@ -400,6 +504,156 @@ mod tests {
         5283751526
         "#;
         let mut grid: Grid<Vec<_>> = INPUT.trim().lines().into();
-        assert_eq!(grid.run_until_synchronized_flash(), 195);
+        assert_eq!(grid.run_until_synchronized_flash(), Some(195));
+    }
+
+    #[test]
+    fn test_run_until_synchronized_never() {
+        let mut grid: Grid<Vec<_>> = "02".lines().into();
+        assert_eq!(grid.run_until_synchronized_flash(), None);
+    }
+
+    #[test]
+    fn test_energy_cycle() {
+        const INPUT: &str = r#"
+        5483143223
+        2745854711
+        5264556173
+        6141336146
+        6357385478
+        4167524645
+        2176841721
+        6882881134
+        4846848554
+        5283751526
+        "#;
+        let grid: Grid<Vec<_>> = INPUT.trim().lines().into();
+        let cycle = grid.energy_cycle().unwrap();
+        assert_eq!((cycle.start, cycle.period), (195, 10));
+        assert_eq!(cycle.state.grid, vec![0; 100]);
+    }
+
+    #[test]
+    fn test_count_flashes_after() {
+        const INPUT: &str = r#"
+        5483143223
+        2745854711
+        5264556173
+        6141336146
+        6357385478
+        4167524645
+        2176841721
+        6882881134
+        4846848554
+        5283751526
+        "#;
+        let mut grid: Grid<Vec<_>> = INPUT.trim().lines().into();
+        assert_eq!(grid.count_flashes_after(100), Some(1656));
+        assert_eq!(
+            grid.count_flashes_after(1000),
+            Some(grid.run_step_count(1000) as u128)
+        );
+        // The grid is synchronized by now, so every octopus flashes once every 10 steps.
+        assert_eq!(
+            grid.count_flashes_after(1_000_000_000_000),
+            Some(100 * 100_000_000_000)
+        );
+    }
+
+    #[test]
+    fn test_run_single_step_orthogonal() {
+        const INPUT: &str = r#"
+        000
+        090
+        000
+        "#;
+        let config = GridConfig {
+            neighbourhood: Neighbourhood::Orthogonal,
+            ..GridConfig::default()
+        };
+        let mut grid: Grid<Vec<_>> = INPUT.trim().lines().into();
+        grid = grid.with_config(config);
+        grid.run_single_step();
+        assert_eq!(grid.grid, vec![1, 2, 1, 2, 0, 2, 1, 2, 1]);
+    }
+
+    #[test]
+    fn test_run_single_step_hex() {
+        const INPUT: &str = r#"
+        000
+        090
+        000
+        "#;
+        let config = GridConfig {
+            neighbourhood: Neighbourhood::Hex,
+            ..GridConfig::default()
+        };
+        let mut grid: Grid<Vec<_>> = INPUT.trim().lines().into();
+        grid = grid.with_config(config);
+        grid.run_single_step();
+        assert_eq!(grid.grid, vec![1, 2, 2, 2, 0, 2, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_run_single_step_wrapping() {
+        const INPUT: &str = r#"
+        9000
+        0000
+        0000
+        "#;
+        let config = GridConfig {
+            edge_behaviour: EdgeBehaviour::Wrapping,
+            ..GridConfig::default()
+        };
+        let mut grid: Grid<Vec<_>> = INPUT.trim().lines().into();
+        grid = grid.with_config(config);
+        grid.run_single_step();
+        assert_eq!(grid.grid, vec![0, 2, 1, 2, 2, 2, 1, 2, 2, 2, 1, 2]);
+    }
+
+    #[test]
+    fn test_run_step_count_with_threshold_and_observer() {
+        const INPUT: &str = r#"
+        10
+        00
+        "#;
+        let config = GridConfig {
+            threshold: 2,
+            ..GridConfig::default()
+        };
+        let mut grid: Grid<Vec<_>> = INPUT.trim().lines().into();
+        grid = grid.with_config(config);
+        assert_eq!(grid.max_value(), 1);
+        let mut steps = vec![];
+        let flash_count = grid.run_step_count_with(2, |step, flashed| {
+            steps.push((step, flashed.to_vec()));
+        });
+        assert_eq!(flash_count, 4);
+        assert_eq!(
+            steps,
+            vec![(1, vec![(0, 0), (1, 0), (0, 1), (1, 1)]), (2, vec![])]
+        );
+        assert_eq!(grid.grid, vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_config_energy_level_at_threshold() {
+        let grid: Grid<Vec<_>> = "19\n11".lines().into();
+        let _ = grid.with_config(GridConfig {
+            threshold: 9,
+            ..GridConfig::default()
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_config_wrapping_hex_odd_height() {
+        let grid: Grid<Vec<_>> = "11\n11\n11".lines().into();
+        let _ = grid.with_config(GridConfig {
+            neighbourhood: Neighbourhood::Hex,
+            edge_behaviour: EdgeBehaviour::Wrapping,
+            ..GridConfig::default()
+        });
     }
 }
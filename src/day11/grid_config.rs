@@ -0,0 +1,21 @@
+use super::{EdgeBehaviour, Neighbourhood};
+
+/// Settings for how the octopuses flash. The defaults are what the puzzle uses, where an
+/// octopus flashes when its energy reaches 10 and increases the energy of the 8 octopuses
+/// around it, with nothing beyond the edges of the grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridConfig {
+    pub threshold: u8,
+    pub neighbourhood: Neighbourhood,
+    pub edge_behaviour: EdgeBehaviour,
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 10,
+            neighbourhood: Neighbourhood::default(),
+            edge_behaviour: EdgeBehaviour::default(),
+        }
+    }
+}
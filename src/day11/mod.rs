@@ -1,10 +1,18 @@
+mod edge_behaviour;
 mod grid;
+mod grid_config;
+mod neighbourhood;
 
-use grid::Grid;
+pub use {
+    edge_behaviour::EdgeBehaviour, grid::Grid, grid_config::GridConfig,
+    neighbourhood::Neighbourhood,
+};
 
 pub fn run(input: &str) -> (u32, u32) {
     let mut grid: Grid<Vec<_>> = input.trim().lines().into();
     let part_1 = grid.run_step_count(100);
-    let part_2 = grid.run_until_synchronized_flash();
+    let part_2 = grid
+        .run_until_synchronized_flash()
+        .expect("The octopuses never synchronize.");
     (part_1, part_2)
 }
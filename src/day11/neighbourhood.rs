@@ -0,0 +1,50 @@
+/// Which cells around a flashing octopus get their energy increased.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Neighbourhood {
+    /// The 4 cells sharing an edge with the cell.
+    Orthogonal,
+    /// The 8 cells sharing an edge or a corner with the cell, which is what the puzzle uses.
+    #[default]
+    Moore,
+    /// The 6 cells around the cell when odd rows are shifted half a cell to the right, so
+    /// each row sits in the gaps of the rows above and below.
+    Hex,
+}
+
+impl Neighbourhood {
+    /// Gets the (x, y) offsets of the neighbours of a cell in the given row.
+    pub fn offsets(&self, row: usize) -> &'static [(isize, isize)] {
+        match self {
+            Self::Orthogonal => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Self::Moore => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+            Self::Hex if row.is_multiple_of(2) => {
+                &[(-1, -1), (0, -1), (-1, 0), (1, 0), (-1, 1), (0, 1)]
+            }
+            Self::Hex => &[(0, -1), (1, -1), (-1, 0), (1, 0), (0, 1), (1, 1)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(Neighbourhood::Orthogonal.offsets(0).len(), 4);
+        assert_eq!(Neighbourhood::Moore.offsets(1).len(), 8);
+        assert!(Neighbourhood::Hex.offsets(0).contains(&(-1, -1)));
+        assert!(!Neighbourhood::Hex.offsets(0).contains(&(1, -1)));
+        assert!(Neighbourhood::Hex.offsets(1).contains(&(1, 1)));
+        assert!(!Neighbourhood::Hex.offsets(1).contains(&(-1, 1)));
+    }
+}
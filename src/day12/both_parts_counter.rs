@@ -0,0 +1,157 @@
+use {
+    super::{CaveSystem, OneSmallCaveTwice, VisitPolicy},
+    std::collections::HashMap,
+};
+
+/// Counts the paths for both parts in a single pass. A path which hasn't used its double visit
+/// yet is in the same state as a part 1 path, so each of those states is counted once to give
+/// both the ways to finish without a double visit and the ways to finish with at most one.
+pub(super) struct BothPartsCounter<'a> {
+    system: &'a CaveSystem,
+    memo: HashMap<(usize, <OneSmallCaveTwice as VisitPolicy>::State), (usize, usize)>,
+}
+
+impl<'a> BothPartsCounter<'a> {
+    /// Prepares to count the paths through the cave system.
+    pub(super) fn new(system: &'a CaveSystem) -> Self {
+        Self {
+            system,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Counts the paths from start to end, first where small caves can only be visited once,
+    /// and then where one small cave on each path can be visited twice. Gives 0 for both if
+    /// there's no start or end cave.
+    pub(super) fn count(&mut self) -> (usize, usize) {
+        match (self.system.id("start"), self.system.id("end")) {
+            (Some(start), Some(end)) => {
+                let state = OneSmallCaveTwice.initial_state(self.system, start);
+                self.count_from(start, state, end)
+            }
+            _ => (0, 0),
+        }
+    }
+
+    // Counts the ways to get to the end from the cave, without and then with a double visit
+    // still allowed. Once the double visit has been used up both counts are the same, and
+    // moves which use it up only add to the second count.
+    fn count_from(
+        &mut self,
+        cave: usize,
+        state: <OneSmallCaveTwice as VisitPolicy>::State,
+        end: usize,
+    ) -> (usize, usize) {
+        if cave == end {
+            return (1, 1);
+        }
+        let key = (cave, state);
+        if let Some(counts) = self.memo.get(&key) {
+            return *counts;
+        }
+        let system = self.system;
+        let used_double_visit = state.1;
+        let counts = system
+            .neighbours(cave)
+            .iter()
+            .filter_map(|next| {
+                OneSmallCaveTwice
+                    .visit(system, &state, *next)
+                    .map(|state| (*next, state))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .fold((0, 0), |(once, twice), (next, next_state)| {
+                let (next_once, next_twice) = self.count_from(next, next_state, end);
+                if next_state.1 == used_double_visit {
+                    (once + next_once, twice + next_twice)
+                } else {
+                    (once, twice + next_twice)
+                }
+            });
+        self.memo.insert(key, counts);
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::day12::{PathCounter, SmallCavesOnce},
+        std::collections::HashSet,
+    };
+
+    const LARGE: &str = "
+        dc-end
+        HN-start
+        start-kj
+        dc-start
+        dc-HN
+        LN-dc
+        HN-end
+        kj-sa
+        kj-HN
+        kj-dc";
+
+    // Finds every state the policy allows a path to be in, other than at the end.
+    fn reachable_states<P: VisitPolicy>(
+        system: &CaveSystem,
+        policy: P,
+    ) -> HashSet<(usize, P::State)> {
+        let (start, end) = (system.id("start").unwrap(), system.id("end").unwrap());
+        let mut seen = HashSet::new();
+        let mut stack = vec![(start, policy.initial_state(system, start))];
+        while let Some((cave, state)) = stack.pop() {
+            if cave == end || !seen.insert((cave, state.clone())) {
+                continue;
+            }
+            for next in system.neighbours(cave) {
+                if let Some(next_state) = policy.visit(system, &state, *next) {
+                    stack.push((*next, next_state));
+                }
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn test_count_matches_path_counters() {
+        let system = CaveSystem::from_links(LARGE.lines());
+        assert_eq!(
+            BothPartsCounter::new(&system).count(),
+            (
+                PathCounter::new(&system, SmallCavesOnce).count(),
+                PathCounter::new(&system, OneSmallCaveTwice).count()
+            )
+        );
+        assert_eq!(BothPartsCounter::new(&system).count(), (19, 103));
+    }
+
+    #[test]
+    fn test_part_1_states_counted_once() {
+        let system = CaveSystem::from_links(LARGE.lines());
+        let mut counter = BothPartsCounter::new(&system);
+        counter.count();
+
+        // The part 1 states are the ones where the double visit hasn't been used, and they're
+        // only remembered once rather than again by a separate part 1 count.
+        let part_1_states = counter
+            .memo
+            .keys()
+            .filter(|(_, (_, used_double_visit))| !used_double_visit)
+            .map(|(cave, (visited, _))| (*cave, *visited))
+            .collect::<HashSet<_>>();
+        assert_eq!(part_1_states, reachable_states(&system, SmallCavesOnce));
+        assert_eq!(
+            counter.memo.len(),
+            reachable_states(&system, OneSmallCaveTwice).len()
+        );
+    }
+
+    #[test]
+    fn test_count_missing_end() {
+        let system = CaveSystem::from_links("start-A\nA-b".lines());
+        assert_eq!(BothPartsCounter::new(&system).count(), (0, 0));
+    }
+}
@@ -0,0 +1,84 @@
+/// The kind of cave that a name refers to, which will either be the special start or end
+/// cave, or a large (uppercase) or small (lowercase) cave.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CaveKind {
+    Start,
+    End,
+    Small,
+    Large,
+}
+
+impl CaveKind {
+    /// Determines the kind of cave from its name. Panics if the name contains anything other
+    /// than alphabetical characters, or mixes uppercase and lowercase characters.
+    pub fn of(name: &str) -> Self {
+        match name {
+            "start" => Self::Start,
+            "end" => Self::End,
+            name => {
+                // Determine if it's a lowercase or upper case string, while detecting
+                // a mismatch and panicing.
+                let is_lowercase = name.chars().fold(None, |is_lowercase, character| {
+                    let char_lowercase = character.is_lowercase();
+                    let char_uppercase = character.is_uppercase();
+                    match (is_lowercase, char_lowercase, char_uppercase) {
+                        (_, false, false) => panic!("Invalid character {} found. Node identifiers must be uppercase or lowercase alphabetical characters only.", character),
+                        (None, true, _) => Some(true),
+                        (None, _, true) => Some(false),
+                        (Some(true), false, _) | (Some(false), true, _) => panic!("Character {} found which doesn't match the established case of the node identifier.", character),
+                        _ => is_lowercase
+                    }
+                }).expect("Cave names can't be empty.");
+
+                if is_lowercase {
+                    Self::Small
+                } else {
+                    Self::Large
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cave_kind_start() {
+        assert_eq!(CaveKind::of("start"), CaveKind::Start);
+    }
+
+    #[test]
+    fn test_cave_kind_end() {
+        assert_eq!(CaveKind::of("end"), CaveKind::End);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cave_kind_panic_invalid_character() {
+        let _ = CaveKind::of("uvh3m");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cave_kind_panic_invalid_casing() {
+        let _ = CaveKind::of("uvhMno");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cave_kind_panic_empty() {
+        let _ = CaveKind::of("");
+    }
+
+    #[test]
+    fn test_cave_kind_small_cave() {
+        assert_eq!(CaveKind::of("uvhmr"), CaveKind::Small);
+    }
+
+    #[test]
+    fn test_cave_kind_large_cave() {
+        assert_eq!(CaveKind::of("UVHMR"), CaveKind::Large);
+    }
+}
@@ -0,0 +1,207 @@
+use {
+    super::{both_parts_counter::BothPartsCounter, CaveKind, Paths, VisitPolicy},
+    crate::common::graph::UndirectedGraph,
+    std::{
+        borrow::Borrow,
+        io::{Result, Write},
+    },
+};
+
+/// The caves and the passages between them, stored as a graph where each cave's name is
+/// interned when it's first seen. A cave's id is its index into the graph and the kinds, so
+/// the rest of the solution only deals with ids but can always get the names back. Small caves
+/// are also numbered separately, so they can be given bits in a bitmask of visited caves
+/// without using bits for the other caves.
+#[derive(Debug, Default, PartialEq)]
+pub struct CaveSystem {
+    graph: UndirectedGraph,
+    kinds: Vec<CaveKind>,
+    small_indices: Vec<Option<usize>>,
+}
+
+impl CaveSystem {
+    /// Builds the cave system from lines in the form "a-b" which link two caves together.
+    /// Empty lines are skipped, and any other line without a hyphen causes a panic.
+    pub fn from_links(iter: impl Iterator<Item = impl Borrow<str>>) -> Self {
+        let graph = UndirectedGraph::from_links(iter).expect(
+            "Expected two components separated by a hyphen to form a link, input string invalid.",
+        );
+        Self::from_graph(graph)
+    }
+
+    /// Builds the cave system from a graph, where each vertex is a cave. Panics if any name
+    /// isn't a valid cave name.
+    pub fn from_graph(graph: UndirectedGraph) -> Self {
+        let kinds = (0..graph.len())
+            .map(|id| CaveKind::of(graph.name(id)))
+            .collect::<Vec<_>>();
+        let mut small_count = 0;
+        let small_indices = kinds
+            .iter()
+            .map(|kind| {
+                (*kind == CaveKind::Small).then(|| {
+                    small_count += 1;
+                    small_count - 1
+                })
+            })
+            .collect();
+        Self {
+            graph,
+            kinds,
+            small_indices,
+        }
+    }
+
+    /// Gets the graph of caves, which can be used to sanity check the input.
+    pub fn graph(&self) -> &UndirectedGraph {
+        &self.graph
+    }
+
+    /// Gets the number of caves.
+    pub fn len(&self) -> usize {
+        self.graph.len()
+    }
+
+    /// Checks whether there are no caves at all.
+    pub fn is_empty(&self) -> bool {
+        self.graph.is_empty()
+    }
+
+    /// Gets the id of the cave with the given name, if there is one.
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.graph.id(name)
+    }
+
+    /// Gets the name of the cave with the given id.
+    pub fn name(&self, id: usize) -> &str {
+        self.graph.name(id)
+    }
+
+    /// Gets the index of the cave among the small caves, in the order they were first seen,
+    /// or None if it isn't a small cave.
+    pub fn small_index(&self, id: usize) -> Option<usize> {
+        self.small_indices[id]
+    }
+
+    /// Gets the kind of the cave with the given id.
+    pub fn kind(&self, id: usize) -> CaveKind {
+        self.kinds[id]
+    }
+
+    /// Gets the ids of the caves linked to the cave with the given id, in the order the
+    /// links were given.
+    pub fn neighbours(&self, id: usize) -> &[usize] {
+        self.graph.neighbours(id)
+    }
+
+    /// Counts the paths from start to end, first where small caves can only be visited once,
+    /// and then where one small cave on each path can be visited twice. Both are counted in a
+    /// single pass, which is much quicker than counting the paths given by the paths iterator.
+    pub fn count_paths(&self) -> (usize, usize) {
+        BothPartsCounter::new(self).count()
+    }
+
+    /// Iterates over every path from start to end which is allowed by the visit policy.
+    /// Gives nothing if there's no start or end cave.
+    pub fn paths<P: VisitPolicy>(&self, policy: P) -> Paths<'_, P> {
+        Paths::new(self, policy)
+    }
+
+    /// Writes the cave system in the Graphviz DOT language. Large caves are drawn as filled
+    /// boxes, small caves as circles, and the start and end caves as double circles.
+    pub fn write_dot(&self, writer: &mut impl Write) -> Result<()> {
+        self.graph.write_dot(writer, |id| {
+            match self.kinds[id] {
+                CaveKind::Start | CaveKind::End => "shape=doublecircle",
+                CaveKind::Large => "shape=box, style=filled",
+                CaveKind::Small => "shape=circle",
+            }
+            .to_string()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn test_from_links_missing_hyphen() {
+        let _ = CaveSystem::from_links("start-end\nblahBLAH".lines());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_links_invalid_name() {
+        let _ = CaveSystem::from_links("start-end\nsTaRt-end".lines());
+    }
+
+    #[test]
+    fn test_from_links_empty() {
+        assert_eq!(
+            CaveSystem::from_links("      ".lines()),
+            CaveSystem::default()
+        );
+    }
+
+    #[test]
+    fn test_from_links() {
+        let system = CaveSystem::from_links(
+            "
+            start-robin
+            robin-ROBIN
+            ROBIN-end
+            start-ROBIN
+            "
+            .lines(),
+        );
+        assert_eq!(system.len(), 4);
+        let ids = ["start", "robin", "ROBIN", "end"].map(|name| system.id(name).unwrap());
+        assert_eq!(ids, [0, 1, 2, 3]);
+        assert_eq!(
+            ids.map(|id| system.kind(id)),
+            [
+                CaveKind::Start,
+                CaveKind::Small,
+                CaveKind::Large,
+                CaveKind::End
+            ]
+        );
+        assert_eq!(
+            ids.map(|id| system.small_index(id)),
+            [None, Some(0), None, None]
+        );
+        assert_eq!(system.name(2), "ROBIN");
+        assert_eq!(system.neighbours(0), &[1, 2]);
+        assert_eq!(system.neighbours(2), &[1, 3, 0]);
+        assert_eq!(system.id("nowhere"), None);
+    }
+
+    #[test]
+    fn test_from_links_repeated_link() {
+        let system = CaveSystem::from_links("start-a\na-end\nend-a".lines());
+        assert_eq!(system.neighbours(1), &[0, 2]);
+        assert_eq!(system.count_paths(), (1, 1));
+    }
+
+    #[test]
+    fn test_write_dot() {
+        let system = CaveSystem::from_links("start-A\nA-b\nb-end".lines());
+        let mut output = vec![];
+        system.write_dot(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            r#"graph {
+    "start" [shape=doublecircle];
+    "A" [shape=box, style=filled];
+    "b" [shape=circle];
+    "end" [shape=doublecircle];
+    "start" -- "A";
+    "A" -- "b";
+    "b" -- "end";
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use super::{visit_policy::cave_bit, CaveKind, CaveSystem, VisitPolicy};
+
+/// The visit policy for part 2, where a single small cave on each path can be visited twice
+/// and the rest only once. The state is a bitmask of the small caves that have been visited,
+/// and whether the double visit has been used up.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OneSmallCaveTwice;
+
+impl VisitPolicy for OneSmallCaveTwice {
+    type State = (u128, bool);
+
+    fn initial_state(&self, _: &CaveSystem, _: usize) -> Self::State {
+        (0, false)
+    }
+
+    fn visit(
+        &self,
+        system: &CaveSystem,
+        (visited, used_double_visit): &Self::State,
+        cave: usize,
+    ) -> Option<Self::State> {
+        match system.kind(cave) {
+            CaveKind::Start => None,
+            CaveKind::End | CaveKind::Large => Some((*visited, *used_double_visit)),
+            CaveKind::Small if visited & cave_bit(system, cave) == 0 => {
+                Some((visited | cave_bit(system, cave), *used_double_visit))
+            }
+            CaveKind::Small if !used_double_visit => Some((*visited, true)),
+            CaveKind::Small => None,
+        }
+    }
+}
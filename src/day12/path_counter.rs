@@ -0,0 +1,125 @@
+use {
+    super::{CaveSystem, VisitPolicy},
+    std::collections::HashMap,
+};
+
+/// Counts the paths through a cave system without enumerating them. The number of ways to
+/// finish a path only depends on the current cave and the path's state for the visit policy,
+/// so each of those is only counted once and remembered, even across calls to count.
+pub struct PathCounter<'a, P: VisitPolicy> {
+    system: &'a CaveSystem,
+    policy: P,
+    memo: HashMap<(usize, P::State), usize>,
+}
+
+impl<'a, P: VisitPolicy> PathCounter<'a, P> {
+    /// Prepares to count the paths through the cave system which are allowed by the policy.
+    pub fn new(system: &'a CaveSystem, policy: P) -> Self {
+        Self {
+            system,
+            policy,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Counts the paths from start to end. Gives 0 if there's no start or end cave.
+    pub fn count(&mut self) -> usize {
+        match (self.system.id("start"), self.system.id("end")) {
+            (Some(start), Some(end)) => {
+                let state = self.policy.initial_state(self.system, start);
+                self.count_from(start, state, end)
+            }
+            _ => 0,
+        }
+    }
+
+    // Counts the ways to get to the end from the cave. This recurses rather than using a
+    // stack, but the depth is limited by the length of the longest path.
+    fn count_from(&mut self, cave: usize, state: P::State, end: usize) -> usize {
+        if cave == end {
+            return 1;
+        }
+        let key = (cave, state);
+        if let Some(count) = self.memo.get(&key) {
+            return *count;
+        }
+        let system = self.system;
+        let count = system
+            .neighbours(cave)
+            .iter()
+            .filter_map(|next| {
+                self.policy
+                    .visit(system, &key.1, *next)
+                    .map(|state| (*next, state))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|(next, state)| self.count_from(next, state, end))
+            .sum();
+        self.memo.insert(key, count);
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::day12::{OneSmallCaveTwice, SmallCavesOnce, SmallCavesUpTo},
+    };
+
+    const LARGE: &str = "
+        dc-end
+        HN-start
+        start-kj
+        dc-start
+        dc-HN
+        LN-dc
+        HN-end
+        kj-sa
+        kj-HN
+        kj-dc";
+
+    #[test]
+    fn test_count_matches_paths() {
+        let system = CaveSystem::from_links(LARGE.lines());
+        let once = PathCounter::new(&system, SmallCavesOnce).count();
+        let twice = PathCounter::new(&system, OneSmallCaveTwice).count();
+        assert_eq!((once, twice), (19, 103));
+        assert_eq!(once, system.paths(SmallCavesOnce).count());
+        assert_eq!(twice, system.paths(OneSmallCaveTwice).count());
+        assert_eq!(
+            PathCounter::new(&system, SmallCavesUpTo(3)).count(),
+            system.paths(SmallCavesUpTo(3)).count()
+        );
+    }
+
+    #[test]
+    fn test_count_missing_end() {
+        let system = CaveSystem::from_links("start-A\nA-b".lines());
+        assert_eq!(PathCounter::new(&system, OneSmallCaveTwice).count(), 0);
+    }
+
+    #[test]
+    fn test_large_caves_dont_use_bits() {
+        let links = (0..200)
+            .flat_map(|idx| {
+                let name = format!("L{}", "A".repeat(idx + 1));
+                [format!("start-{}", name), format!("{}-end", name)]
+            })
+            .collect::<Vec<_>>();
+        let system = CaveSystem::from_links(links.iter().map(String::as_str));
+        assert_eq!(PathCounter::new(&system, SmallCavesOnce).count(), 200);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_too_many_caves_for_bitmask() {
+        let links = (0..129)
+            .map(|idx| format!("start-c{}", "a".repeat(idx + 1)))
+            .chain(std::iter::once("start-end".to_string()))
+            .collect::<Vec<_>>();
+        let system = CaveSystem::from_links(links.iter().map(String::as_str));
+        let _ = PathCounter::new(&system, SmallCavesOnce).count();
+    }
+}
@@ -0,0 +1,160 @@
+use super::{CaveSystem, VisitPolicy};
+
+/// Iterator over the paths from start to end through a cave system, giving each path as the
+/// names of the caves along it. This searches depth first using a stack rather than
+/// recursion, and every partial path on the stack carries its state for the visit policy,
+/// which decides where it can go next. A path stops as soon as it reaches the end.
+pub struct Paths<'a, P: VisitPolicy> {
+    system: &'a CaveSystem,
+    policy: P,
+    end: Option<usize>,
+    stack: Vec<(Vec<usize>, P::State)>,
+}
+
+impl<'a, P: VisitPolicy> Paths<'a, P> {
+    /// Starts the search for paths through the cave system.
+    pub fn new(system: &'a CaveSystem, policy: P) -> Self {
+        let (start, end) = (system.id("start"), system.id("end"));
+        Self {
+            system,
+            end,
+            stack: start
+                .filter(|_| end.is_some())
+                .map(|start| (vec![start], policy.initial_state(system, start)))
+                .into_iter()
+                .collect(),
+            policy,
+        }
+    }
+}
+
+impl<'a, P: VisitPolicy> Iterator for Paths<'a, P> {
+    type Item = Vec<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((path, state)) = self.stack.pop() {
+            let cave = *path.last().expect("paths are never empty");
+            if Some(cave) == self.end {
+                return Some(path.iter().map(|id| self.system.name(*id)).collect());
+            }
+            // Push the neighbours in reverse so that they're explored in the order they were
+            // linked, which keeps the order of the paths stable.
+            self.system
+                .neighbours(cave)
+                .iter()
+                .rev()
+                .filter_map(|next| {
+                    self.policy
+                        .visit(self.system, &state, *next)
+                        .map(|state| (*next, state))
+                })
+                .for_each(|(next, state)| {
+                    let mut path = path.clone();
+                    path.push(next);
+                    self.stack.push((path, state));
+                });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::day12::{OneSmallCaveTwice, SmallCavesOnce, SmallCavesUpTo},
+    };
+
+    const SMALL: &str = "
+        start-A
+        start-b
+        A-c
+        A-b
+        b-d
+        A-end
+        b-end";
+
+    #[test]
+    fn test_paths_without_double_visit() {
+        let system = CaveSystem::from_links(SMALL.lines());
+        let mut paths = system
+            .paths(SmallCavesOnce)
+            .map(|path| path.join(","))
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "start,A,b,A,c,A,end",
+                "start,A,b,A,end",
+                "start,A,b,end",
+                "start,A,c,A,b,A,end",
+                "start,A,c,A,b,end",
+                "start,A,c,A,end",
+                "start,A,end",
+                "start,b,A,c,A,end",
+                "start,b,A,end",
+                "start,b,end",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paths_with_double_visit() {
+        let system = CaveSystem::from_links(SMALL.lines());
+        let paths = system.paths(OneSmallCaveTwice).collect::<Vec<_>>();
+        assert_eq!(paths.len(), 36);
+        assert!(paths.contains(&vec!["start", "A", "b", "A", "b", "A", "c", "A", "end"]));
+        assert!(paths.iter().all(|path| {
+            let small = path
+                .iter()
+                .filter(|name| name.chars().all(char::is_lowercase));
+            small.clone().count() - small.collect::<std::collections::HashSet<_>>().len() <= 1
+        }));
+    }
+
+    #[test]
+    fn test_paths_missing_start_or_end() {
+        let system = CaveSystem::from_links("start-A\nA-b".lines());
+        assert_eq!(system.paths(OneSmallCaveTwice).count(), 0);
+        let system = CaveSystem::from_links("A-end".lines());
+        assert_eq!(system.paths(SmallCavesOnce).count(), 0);
+    }
+
+    #[test]
+    fn test_paths_small_caves_up_to() {
+        let system = CaveSystem::from_links(SMALL.lines());
+        assert_eq!(system.paths(SmallCavesUpTo(1)).count(), 10);
+        let paths = system.paths(SmallCavesUpTo(2)).collect::<Vec<_>>();
+        assert!(paths.len() > 36);
+        assert!(paths.contains(&vec![
+            "start", "b", "A", "b", "A", "c", "A", "c", "A", "end"
+        ]));
+    }
+
+    // A custom policy which only allows paths of up to a given number of caves, to make sure
+    // paths can't go on forever when two large caves are linked together.
+    struct MaxLength(usize);
+
+    impl VisitPolicy for MaxLength {
+        type State = usize;
+
+        fn initial_state(&self, _: &CaveSystem, _: usize) -> Self::State {
+            1
+        }
+
+        fn visit(&self, system: &CaveSystem, length: &Self::State, cave: usize) -> Option<usize> {
+            (*length < self.0 && system.name(cave) != "start").then_some(length + 1)
+        }
+    }
+
+    #[test]
+    fn test_paths_custom_policy() {
+        let system = CaveSystem::from_links("start-A\nA-B\nB-end".lines());
+        let paths = system
+            .paths(MaxLength(6))
+            .map(|path| path.join(","))
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["start,A,B,A,B,end", "start,A,B,end"]);
+    }
+}
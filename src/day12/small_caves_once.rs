@@ -0,0 +1,30 @@
+use super::{visit_policy::cave_bit, CaveKind, CaveSystem, VisitPolicy};
+
+/// The visit policy for part 1, where each small cave can only be visited once. The state is a
+/// bitmask of the small caves that have been visited, so there can be at most 128 small caves.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SmallCavesOnce;
+
+impl VisitPolicy for SmallCavesOnce {
+    type State = u128;
+
+    fn initial_state(&self, _: &CaveSystem, _: usize) -> Self::State {
+        0
+    }
+
+    fn visit(
+        &self,
+        system: &CaveSystem,
+        visited: &Self::State,
+        cave: usize,
+    ) -> Option<Self::State> {
+        match system.kind(cave) {
+            CaveKind::Start => None,
+            CaveKind::End | CaveKind::Large => Some(*visited),
+            CaveKind::Small if visited & cave_bit(system, cave) == 0 => {
+                Some(visited | cave_bit(system, cave))
+            }
+            CaveKind::Small => None,
+        }
+    }
+}
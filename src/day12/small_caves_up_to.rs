@@ -0,0 +1,27 @@
+use super::{CaveKind, CaveSystem, VisitPolicy};
+
+/// A visit policy where every small cave can be visited up to the given number of times. The
+/// state is the number of times each cave has been visited, indexed by the cave id.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SmallCavesUpTo(pub u8);
+
+impl VisitPolicy for SmallCavesUpTo {
+    type State = Vec<u8>;
+
+    fn initial_state(&self, system: &CaveSystem, _: usize) -> Self::State {
+        vec![0; system.len()]
+    }
+
+    fn visit(&self, system: &CaveSystem, counts: &Self::State, cave: usize) -> Option<Self::State> {
+        match system.kind(cave) {
+            CaveKind::Start => None,
+            CaveKind::End | CaveKind::Large => Some(counts.clone()),
+            CaveKind::Small if counts[cave] < self.0 => {
+                let mut counts = counts.clone();
+                counts[cave] += 1;
+                Some(counts)
+            }
+            CaveKind::Small => None,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use {super::CaveSystem, std::hash::Hash};
+
+/// Decides whether a path through the caves may be extended into another cave. Each path
+/// carries a state which holds whatever the policy needs to remember about where it has been,
+/// and the state is all that's used to decide where it can go next. This means paths with the
+/// same state in the same cave have the same ways to finish, which the path counter relies on.
+pub trait VisitPolicy {
+    type State: Clone + Eq + Hash;
+
+    /// Gets the state of a path which has only visited the start cave.
+    fn initial_state(&self, system: &CaveSystem, start: usize) -> Self::State;
+
+    /// Decides whether a path in the given state can move into the cave, giving the state of
+    /// the path after the move if it can or None if it can't.
+    fn visit(&self, system: &CaveSystem, state: &Self::State, cave: usize) -> Option<Self::State>;
+}
+
+// Gets the bit for a small cave in a bitmask of visited small caves, using its index among
+// the small caves so other caves don't use up bits. Panics if there are more small caves than
+// bits, or the cave isn't small.
+pub(super) fn cave_bit(system: &CaveSystem, cave: usize) -> u128 {
+    let index = system
+        .small_index(cave)
+        .expect("Only small caves have a bit in the visited bitmask.");
+    assert!(
+        index < u128::BITS as usize,
+        "Small cave {} doesn't fit in a bitmask of visited caves, as there are more than {}.",
+        system.name(cave),
+        u128::BITS
+    );
+    1 << index
+}
@@ -1,56 +1,200 @@
 mod paper;
+mod parse_input_error;
 
-use paper::PaperBuilder;
+use {crate::common::ocr::read_text, paper::PaperBuilder};
+
+pub use {
+    paper::{
+        Axis, BuildPaperError, Dot, FoldInstruction, FoldPaperError, Paper,
+        ParseFoldInstructionError,
+    },
+    parse_input_error::ParseInputError,
+};
 
 pub fn run(input: &str) -> (usize, String) {
-    // Build the transparent sheet of paper and place the dots.
-    let mut builder = PaperBuilder::default();
-    let mut lines = input.trim().lines();
-    while let Some(line) = lines.next() {
-        let line = line.trim();
-        if line.is_empty() {
-            break; // all coordinates of dots provided.
-        }
-
-        let mut splits = line.split(",");
-        let x = splits.next().unwrap().parse::<usize>().unwrap();
-        let y = splits.next().unwrap().parse::<usize>().unwrap();
-        builder.place_dot(x, y);
-    }
-
-    // Prepare the iterator that will do the folding as we iterate over it.
-    let mut paper = builder.build().unwrap();
-    let mut fold_ops = lines.map(|line| {
-        let line = line.trim();
-        let fold_component = line.split(" ").skip(2).next().unwrap().trim(); // skips "fold along" text
-        let mut splits = fold_component.split("=");
-        let axis = splits.next().unwrap();
-        let coordinate = splits.next().unwrap().parse::<usize>().unwrap();
-        (axis, coordinate)
-    });
+    let (mut paper, folds) = parse_input(input).unwrap();
 
     // Part 1 is how many dots are present after only a single fold.
-    let part_1 = {
-        let (axis, coordinate) = fold_ops.next().unwrap();
-        if axis == "x" {
-            paper.fold_horizontally(coordinate);
-        } else if axis == "y" {
-            paper.fold_vertically(coordinate);
-        }
-        paper.count_dots()
-    };
+    paper.fold(folds[0]).unwrap();
+    let part_1 = paper.count_dots();
 
     // Finish applying the fold instructions.
-    fold_ops.for_each(|(axis, coordinate)| {
-        if axis == "x" {
-            paper.fold_horizontally(coordinate);
-        } else if axis == "y" {
-            paper.fold_vertically(coordinate);
-        }
-    });
+    folds[1..]
+        .iter()
+        .for_each(|fold| paper.fold(*fold).unwrap());
 
-    // Part 2 will be the displayed grid, we can use the Display implementation of Paper here.
-    let part_2 = format!("\n{}", paper);
+    // Part 2 is the code drawn by the dots. If it can't be read, say why and fall back to
+    // showing the grid using the Display implementation of Paper so it can still be read by eye.
+    let part_2 = read_text(&paper).unwrap_or_else(|error| format!("\n{}\n{}", error, paper));
 
     (part_1, part_2)
 }
+
+/// Parses the input into the sheet of paper with its dots placed, and the fold instructions
+/// in the order they should be made. The dots are separated from the folds by a blank line,
+/// and any blank lines at the start or end of the input are skipped.
+pub fn parse_input(input: &str) -> Result<(Paper, Vec<FoldInstruction>), ParseInputError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| (idx + 1, text.trim()))
+        .skip_while(|(_, text)| text.is_empty());
+
+    // Build the transparent sheet of paper and place the dots.
+    let mut builder = PaperBuilder::default();
+    lines
+        .by_ref()
+        .take_while(|(_, text)| !text.is_empty())
+        .try_for_each(|(line, text)| {
+            let dot = text
+                .parse::<Dot>()
+                .map_err(|error| ParseInputError::InvalidDot { line, error })?;
+            builder.place_dot(dot.x(), dot.y());
+            Ok(())
+        })?;
+    let paper = builder.build().ok_or(ParseInputError::NoDots)?;
+
+    let folds = lines
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(line, text)| {
+            text.parse()
+                .map_err(|error| ParseInputError::InvalidFold { line, error })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if folds.is_empty() {
+        Err(ParseInputError::NoFolds)
+    } else {
+        Ok((paper, folds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::common::math::ParseVectorError};
+
+    const INPUT: &str = "
+        6,10
+        0,14
+        9,10
+        0,3
+        10,4
+        4,11
+        6,0
+        6,12
+        4,1
+        0,13
+        10,12
+        3,4
+        3,0
+        8,4
+        1,10
+        2,14
+        8,10
+        9,0
+
+        fold along y=7
+        fold along x=5
+        ";
+
+    #[test]
+    fn test_run() {
+        let (part_1, part_2) = run(INPUT);
+        assert_eq!(part_1, 17);
+        assert_eq!(
+            part_2,
+            "\nReadTextError::InvalidHeight { height: 5 }\n#####\n#...#\n#...#\n#...#\n#####\n.....\n.....\n"
+        );
+    }
+
+    // Gives the input for a picture drawn upside down below row 6, so that folding along that
+    // row turns it the right way up.
+    fn folded_picture_input(picture: &[&str]) -> String {
+        picture
+            .iter()
+            .enumerate()
+            .flat_map(|(row, text)| {
+                text.char_indices()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(col, _)| format!("{},{}\n", col, 12 - row))
+            })
+            .chain(std::iter::once("\nfold along y=6".to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_run_reads_letters() {
+        let input = folded_picture_input(&[
+            "..##..##..#..#.####",
+            "...#.#..#.#.#..#...",
+            "...#.#..#.##...###.",
+            "...#.#..#.#.#..#...",
+            "#..#.#..#.#.#..#...",
+            ".##...##..#..#.####",
+        ]);
+        assert_eq!(run(&input), (47, "JOKE".to_string()));
+    }
+
+    #[test]
+    fn test_run_unknown_glyph() {
+        let input = folded_picture_input(&[
+            "..##..##..#..#.####",
+            "...#.#..#.#.#..####",
+            "...#.#..#.##...####",
+            "...#.#..#.#.#..####",
+            "#..#.#..#.#.#..####",
+            ".##...##..#..#.####",
+        ]);
+        let (_, part_2) = run(&input);
+        assert!(part_2.starts_with("\nReadTextError::UnknownGlyph { index: 3, column: 15 }\n"));
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let (paper, folds) = parse_input(INPUT).unwrap();
+        assert_eq!(paper.count_dots(), 18);
+        assert_eq!(
+            folds,
+            vec![
+                FoldInstruction::new(Axis::Y, 7),
+                FoldInstruction::new(Axis::X, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_input_invalid_dot() {
+        assert_eq!(
+            parse_input("\n1,2\n3\n\nfold along x=1"),
+            Err(ParseInputError::InvalidDot {
+                line: 3,
+                error: ParseVectorError::NotEnoughParts
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_input_invalid_fold() {
+        assert_eq!(
+            parse_input("1,2\n3,4\n\nfold along y=2\nfold along w=1"),
+            Err(ParseInputError::InvalidFold {
+                line: 5,
+                error: ParseFoldInstructionError::InvalidAxis {
+                    axis: "w".to_string()
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_input_missing_sections() {
+        assert_eq!(
+            parse_input("\n\nfold along x=1"),
+            Err(ParseInputError::InvalidDot {
+                line: 3,
+                error: ParseVectorError::NotEnoughParts
+            })
+        );
+        assert_eq!(parse_input(""), Err(ParseInputError::NoDots));
+        assert_eq!(parse_input("1,2\n\n"), Err(ParseInputError::NoFolds));
+    }
+}
@@ -0,0 +1,7 @@
+/// The axis that a fold line runs across. Folding along X folds the paper to the left around
+/// a vertical line, and folding along Y folds it up around a horizontal line.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Axis {
+    X,
+    Y,
+}
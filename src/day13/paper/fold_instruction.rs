@@ -0,0 +1,170 @@
+use {
+    super::{Axis, Dot, ParseFoldInstructionError},
+    crate::common::math::Vector,
+    std::str::FromStr,
+};
+
+/// A single fold of the paper along the line at the given coordinate of the axis.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FoldInstruction {
+    pub axis: Axis,
+    pub line: usize,
+}
+
+impl FoldInstruction {
+    /// Constructs a new fold along the given line.
+    pub fn new(axis: Axis, line: usize) -> Self {
+        Self { axis, line }
+    }
+
+    /// Calculates the width and height of a sheet of the given size after this fold. The
+    /// folded sheet is as big as the larger of the two sides, without the fold line itself.
+    pub fn folded_size(&self, width: usize, height: usize) -> (usize, usize) {
+        match self.axis {
+            Axis::X => (self.folded_length(width), height),
+            Axis::Y => (width, self.folded_length(height)),
+        }
+    }
+
+    /// Finds where a dot ends up after this fold, on a sheet of the given size. Dots on the
+    /// fold line itself are lost so give None. If the far side is bigger then the near side
+    /// gets moved along to line up with it.
+    pub fn apply(&self, dot: Dot, width: usize, height: usize) -> Option<Dot> {
+        let [x, y] = dot.into_array();
+        match self.axis {
+            Axis::X => self.fold_coordinate(x, width).map(|x| Vector::new([x, y])),
+            Axis::Y => self.fold_coordinate(y, height).map(|y| Vector::new([x, y])),
+        }
+    }
+
+    /// Checks whether the dot lies on the fold line.
+    pub fn is_on_line(&self, dot: &Dot) -> bool {
+        match self.axis {
+            Axis::X => dot.x() == self.line,
+            Axis::Y => dot.y() == self.line,
+        }
+    }
+
+    fn folded_length(&self, length: usize) -> usize {
+        self.line.max(length - 1 - self.line)
+    }
+
+    fn fold_coordinate(&self, coordinate: usize, length: usize) -> Option<usize> {
+        let folded_length = self.folded_length(length);
+        if coordinate < self.line {
+            Some(coordinate + folded_length - self.line)
+        } else if coordinate > self.line {
+            Some(folded_length + self.line - coordinate)
+        } else {
+            None
+        }
+    }
+}
+
+// Parses an instruction in the form "fold along x=5" or "fold along y=7".
+impl FromStr for FoldInstruction {
+    type Err = ParseFoldInstructionError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let (axis, line) = string
+            .trim()
+            .strip_prefix("fold along ")
+            .ok_or(ParseFoldInstructionError::MissingPrefix)?
+            .split_once('=')
+            .ok_or(ParseFoldInstructionError::MissingEquals)?;
+        let axis = match axis.trim() {
+            "x" => Axis::X,
+            "y" => Axis::Y,
+            axis => {
+                return Err(ParseFoldInstructionError::InvalidAxis {
+                    axis: axis.to_string(),
+                })
+            }
+        };
+        let line = line
+            .trim()
+            .parse()
+            .map_err(ParseFoldInstructionError::ParseLineError)?;
+        Ok(Self { axis, line })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folded_size() {
+        assert_eq!(FoldInstruction::new(Axis::X, 2).folded_size(5, 7), (2, 7));
+        assert_eq!(FoldInstruction::new(Axis::Y, 1).folded_size(5, 7), (5, 5));
+    }
+
+    #[test]
+    fn test_apply_near_side_larger() {
+        let fold = FoldInstruction::new(Axis::Y, 3);
+        assert_eq!(
+            fold.apply(Vector::new([1, 0]), 2, 5),
+            Some(Vector::new([1, 0]))
+        );
+        assert_eq!(
+            fold.apply(Vector::new([1, 4]), 2, 5),
+            Some(Vector::new([1, 2]))
+        );
+        assert_eq!(fold.apply(Vector::new([1, 3]), 2, 5), None);
+    }
+
+    #[test]
+    fn test_apply_far_side_larger() {
+        let fold = FoldInstruction::new(Axis::X, 1);
+        assert_eq!(
+            fold.apply(Vector::new([0, 3]), 5, 4),
+            Some(Vector::new([2, 3]))
+        );
+        assert_eq!(
+            fold.apply(Vector::new([2, 3]), 5, 4),
+            Some(Vector::new([2, 3]))
+        );
+        assert_eq!(
+            fold.apply(Vector::new([3, 3]), 5, 4),
+            Some(Vector::new([1, 3]))
+        );
+        assert_eq!(
+            fold.apply(Vector::new([4, 3]), 5, 4),
+            Some(Vector::new([0, 3]))
+        );
+    }
+
+    #[test]
+    fn test_fold_instruction_from_str() {
+        assert_eq!(
+            "fold along x=655".parse(),
+            Ok(FoldInstruction::new(Axis::X, 655))
+        );
+        assert_eq!(
+            " fold along y=7 ".parse(),
+            Ok(FoldInstruction::new(Axis::Y, 7))
+        );
+    }
+
+    #[test]
+    fn test_fold_instruction_from_str_errors() {
+        assert_eq!(
+            "fold x=655".parse::<FoldInstruction>(),
+            Err(ParseFoldInstructionError::MissingPrefix)
+        );
+        assert_eq!(
+            "fold along x655".parse::<FoldInstruction>(),
+            Err(ParseFoldInstructionError::MissingEquals)
+        );
+        assert_eq!(
+            "fold along z=655".parse::<FoldInstruction>(),
+            Err(ParseFoldInstructionError::InvalidAxis {
+                axis: "z".to_string()
+            })
+        );
+        assert!(matches!(
+            "fold along y=-1".parse::<FoldInstruction>(),
+            Err(ParseFoldInstructionError::ParseLineError(_))
+        ));
+    }
+}
@@ -0,0 +1,35 @@
+use {
+    super::Dot,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter, Result},
+    },
+};
+
+/// Error cases for folding a sheet of paper, where the paper is left as it was.
+#[derive(Debug, PartialEq)]
+pub enum FoldPaperError {
+    LineOutOfRange { line: usize, length: usize },
+    DotOnLine { dot: Dot },
+}
+
+impl Display for FoldPaperError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::LineOutOfRange { line, length } => write!(
+                f,
+                "FoldPaperError::LineOutOfRange {{ line: {}, length: {} }}",
+                line, length
+            ),
+            Self::DotOnLine { dot } => {
+                write!(
+                    f,
+                    "FoldPaperError::DotOnLine {{ dot: {:?} }}",
+                    dot.as_array()
+                )
+            }
+        }
+    }
+}
+
+impl Error for FoldPaperError {}
@@ -1,167 +1,203 @@
+mod axis;
 mod build_paper_error;
+mod fold_instruction;
+mod fold_paper_error;
 mod paper_builder;
+mod parse_fold_instruction_error;
 
-pub use paper_builder::PaperBuilder;
 use {
-    build_paper_error::BuildPaperError,
+    crate::common::{math::Vector, netpbm::Bitmap},
     std::{
-        cmp::max,
+        collections::{HashMap, HashSet},
         fmt,
         fmt::{Display, Formatter},
-        iter::{once, repeat},
+        iter::once,
     },
-}; // required as we use the Builder to construct the sheet of paper.
+};
+pub use {
+    axis::Axis, build_paper_error::BuildPaperError, fold_instruction::FoldInstruction,
+    fold_paper_error::FoldPaperError, paper_builder::PaperBuilder,
+    parse_fold_instruction_error::ParseFoldInstructionError,
+};
+
+/// The position of a dot on the paper, as (x, y) from the top left.
+pub type Dot = Vector<usize, 2>;
 
 /// Struct that represents the transparent sheet of foldable paper.
-/// Internally this stores a vector of vectors of booleans indicating if the
-/// cell has a dot in it or not. Allows us to fold up the paper either vertically or horizontally.
+/// Internally this only stores the positions of the dots, so a fold just moves each dot to
+/// where it lands. The sheet also remembers its dots from before it was folded and the folds
+/// that were made, so that folds can be undone and each dot traced back to where it started.
 #[derive(Debug, PartialEq)]
 pub struct Paper {
-    storage: Vec<Vec<bool>>,
+    dots: HashSet<Dot>,
     width: usize,
     height: usize,
+    unfolded_dots: HashSet<Dot>,
+    unfolded_size: (usize, usize),
+    folds: Vec<FoldInstruction>,
 }
 
 impl Paper {
     /// This attempts to construct a new sheet of paper from the given
-    /// 2D grid of boolean values. Will return None if the given grid isn't
+    /// 2D grid of boolean values. Will return an error if the given grid isn't
     /// rectangular or if there are no entries in either direction.
     fn new(storage: Vec<Vec<bool>>) -> Result<Self, BuildPaperError> {
         let height = storage.len();
+        let width = storage.first().map_or(0, |line| line.len());
+        if width != 0 && storage.iter().any(|line| line.len() != width) {
+            return Err(BuildPaperError::NonEqualWidth);
+        }
+        let dots = storage
+            .into_iter()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.into_iter()
+                    .enumerate()
+                    .filter(|(_, has_dot)| *has_dot)
+                    .map(move |(x, _)| Vector::new([x, y]))
+            })
+            .collect();
+        Self::from_dots(dots, width, height)
+    }
+
+    /// Constructs a new unfolded sheet of paper of the given size with dots at the given
+    /// positions. Will return an error if there are no entries in either direction.
+    fn from_dots(dots: HashSet<Dot>, width: usize, height: usize) -> Result<Self, BuildPaperError> {
         if height == 0 {
             Err(BuildPaperError::InvalidHeight)
+        } else if width == 0 {
+            Err(BuildPaperError::InvalidWidth)
         } else {
-            let width = storage[0].len();
-            if width == 0 {
-                Err(BuildPaperError::InvalidWidth)
-            } else if storage.iter().skip(1).all(|line| line.len() == width) {
-                Ok(Self {
-                    storage,
-                    width,
-                    height,
-                })
-            } else {
-                Err(BuildPaperError::NonEqualWidth)
-            }
+            Ok(Self {
+                unfolded_dots: dots.clone(),
+                dots,
+                width,
+                height,
+                unfolded_size: (width, height),
+                folds: vec![],
+            })
         }
     }
 
+    /// Constructs a sheet of paper with a dot wherever the bitmap's pixel is on. Fails if the
+    /// bitmap has no pixels in either direction.
+    pub fn from_bitmap(bitmap: &impl Bitmap) -> Result<Self, BuildPaperError> {
+        Self::new(
+            (0..bitmap.height())
+                .map(|row| {
+                    (0..bitmap.width())
+                        .map(|col| bitmap.pixel(col, row))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
     /// Function that folds the sheet of paper UPWARDS around a given line. The line
     /// will not be included in the resulting folded paper and lines below it are flipped
     /// and merged onto lines above it.
-    pub fn fold_vertically(&mut self, around: usize) {
-        let bottom_section_height = self.height - 1 - around;
-        let top_section_height = around;
-        let max_section_height = max(bottom_section_height, top_section_height);
-        let top_section_padding = max_section_height - top_section_height;
-        let bottom_section_padding = max_section_height - bottom_section_height;
-
-        // The paper is folded upwards so the top section remains ordered the same, but padding
-        // lines are added before it to make both sections equal length for the zip.
-        let padding_top = repeat(
-            repeat(false)
-                .take(self.width)
-                .collect::<Vec<_>>()
-                .into_iter(),
-        )
-        .take(top_section_padding);
-        let selection_top = self
-            .storage
-            .iter()
-            .map(|line| {
-                line.into_iter()
-                    .map(|elem| *elem)
-                    .collect::<Vec<_>>()
-                    .into_iter()
-            })
-            .take(top_section_height);
-        let iter_top = padding_top.chain(selection_top);
-
-        // The bottom section will be reversed, but padding still added before it.
-        let padding_bottom = repeat(
-            repeat(false)
-                .take(self.width)
-                .collect::<Vec<_>>()
-                .into_iter(),
-        )
-        .take(bottom_section_padding);
-        let selection_bottom = self
-            .storage
-            .iter()
-            .rev()
-            .map(|line| {
-                line.into_iter()
-                    .map(|elem| *elem)
-                    .collect::<Vec<_>>()
-                    .into_iter()
-            })
-            .take(bottom_section_height);
-        let iter_bottom = padding_bottom.chain(selection_bottom);
-
-        // Zip these together as they're the same length now, each entry becomes the OR operation on both input entries.
-        self.storage = iter_top
-            .zip(iter_bottom)
-            .map(|(line_1_iter, line_2_iter)| {
-                line_1_iter
-                    .zip(line_2_iter)
-                    .map(|(elem_1, elem_2)| elem_1 || elem_2)
-                    .collect()
-            })
-            .collect();
-        self.height = max_section_height;
+    pub fn fold_vertically(&mut self, around: usize) -> Result<(), FoldPaperError> {
+        self.fold(FoldInstruction::new(Axis::Y, around))
     }
 
     /// Folds the paper to the left around a given point horizontally.
-    pub fn fold_horizontally(&mut self, around: usize) {
-        let right_section_width = self.width - 1 - around;
-        let left_section_width = around;
-        let max_section_width = max(left_section_width, right_section_width);
-        let left_section_padding = max_section_width - left_section_width;
-        let right_section_padding = max_section_width - right_section_width;
-
-        self.storage = self
-            .storage
+    pub fn fold_horizontally(&mut self, around: usize) -> Result<(), FoldPaperError> {
+        self.fold(FoldInstruction::new(Axis::X, around))
+    }
+
+    /// Applies the fold to every dot and records it in the fold log. Fails without changing
+    /// the paper if the fold line is outside of the paper, or if there's a dot on the fold
+    /// line since it would be lost. If there are several then the smallest is reported.
+    pub fn fold(&mut self, fold: FoldInstruction) -> Result<(), FoldPaperError> {
+        let (width, height) = (self.width, self.height);
+        let length = match fold.axis {
+            Axis::X => width,
+            Axis::Y => height,
+        };
+        if fold.line >= length {
+            return Err(FoldPaperError::LineOutOfRange {
+                line: fold.line,
+                length,
+            });
+        }
+        if let Some(dot) = self.dots.iter().filter(|dot| fold.is_on_line(dot)).min() {
+            return Err(FoldPaperError::DotOnLine { dot: *dot });
+        }
+        self.dots = self
+            .dots
             .iter()
-            .map(|line| {
-                let padding_left = repeat(false).take(left_section_padding);
-                let selection_left = line.iter().map(|elem| *elem).take(left_section_width);
-                let iter_left = padding_left.chain(selection_left);
-
-                let padding_right = repeat(false).take(right_section_padding);
-                let selection_right = line
-                    .iter()
-                    .rev()
-                    .map(|elem| *elem)
-                    .take(right_section_width);
-                let iter_right = padding_right.chain(selection_right);
-
-                iter_left
-                    .zip(iter_right)
-                    .map(|(elem_1, elem_2)| elem_1 || elem_2)
-                    .collect()
-            })
+            .filter_map(|dot| fold.apply(*dot, width, height))
             .collect();
-        self.width = max_section_width;
+        (self.width, self.height) = fold.folded_size(width, height);
+        self.folds.push(fold);
+        Ok(())
+    }
+
+    /// Gets the folds which have been made, in the order they were made in.
+    pub fn folds(&self) -> &[FoldInstruction] {
+        &self.folds
     }
 
-    /// Counts the number of dots (true values) present in the grid
+    /// Undoes the last fold, giving it back, or None if the paper hasn't been folded. The
+    /// dots are put back where they were before that fold by replaying the earlier folds
+    /// on the unfolded sheet.
+    pub fn unfold(&mut self) -> Option<FoldInstruction> {
+        let fold = self.folds.pop()?;
+        (self.width, self.height) = self.unfolded_size;
+        self.dots = self.unfolded_dots.clone();
+        std::mem::take(&mut self.folds)
+            .into_iter()
+            .for_each(|fold| self.fold(fold).expect("the fold was already made"));
+        Some(fold)
+    }
+
+    /// Maps each dot on the folded sheet to the (sorted) positions on the unfolded sheet of
+    /// every dot that was folded onto it.
+    pub fn origins(&self) -> HashMap<Dot, Vec<Dot>> {
+        let mut origins = HashMap::<_, Vec<_>>::new();
+        self.unfolded_dots.iter().for_each(|origin| {
+            let folded = self.folds.iter().try_fold(
+                (*origin, self.unfolded_size),
+                |(dot, (width, height)), fold| {
+                    fold.apply(dot, width, height)
+                        .map(|dot| (dot, fold.folded_size(width, height)))
+                },
+            );
+            if let Some((dot, _)) = folded {
+                origins.entry(dot).or_default().push(*origin);
+            }
+        });
+        origins.values_mut().for_each(|origins| origins.sort());
+        origins
+    }
+
+    /// Counts the number of dots present in the grid
     pub fn count_dots(&self) -> usize {
-        self.storage
-            .iter()
-            .map(|line| line.into_iter().filter(|elem| **elem).count())
-            .sum()
+        self.dots.len()
+    }
+}
+
+impl Bitmap for Paper {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, col: usize, row: usize) -> bool {
+        self.dots.contains(&Vector::new([col, row]))
     }
 }
 
 impl Display for Paper {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.write_str(
-            &self
-                .storage
-                .iter()
-                .flat_map(|line| {
-                    line.into_iter()
-                        .map(|has_dot| if *has_dot { '#' } else { '.' })
+            &(0..self.height)
+                .flat_map(|row| {
+                    (0..self.width)
+                        .map(move |col| if self.pixel(col, row) { '#' } else { '.' })
                         .chain(once('\n'))
                 })
                 .collect::<String>(),
@@ -171,7 +207,42 @@ impl Display for Paper {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, crate::common::netpbm::NetpbmImage};
+
+    // Gets the dots as a dense grid, which is easier to compare against.
+    fn grid(paper: &Paper) -> Vec<Vec<bool>> {
+        (0..paper.height)
+            .map(|row| (0..paper.width).map(|col| paper.pixel(col, row)).collect())
+            .collect()
+    }
+
+    // The sheet from the puzzle example.
+    fn example() -> Paper {
+        let mut builder = PaperBuilder::default();
+        [
+            (6, 10),
+            (0, 14),
+            (9, 10),
+            (0, 3),
+            (10, 4),
+            (4, 11),
+            (6, 0),
+            (6, 12),
+            (4, 1),
+            (0, 13),
+            (10, 12),
+            (3, 4),
+            (3, 0),
+            (8, 4),
+            (1, 10),
+            (2, 14),
+            (8, 10),
+            (9, 0),
+        ]
+        .into_iter()
+        .for_each(|(x, y)| builder.place_dot(x, y));
+        builder.build().unwrap()
+    }
 
     #[test]
     fn test_new_paper_invalid_height() {
@@ -193,27 +264,27 @@ mod tests {
 
     #[test]
     fn test_new_paper_success() {
+        let paper = Paper::new(vec![vec![true, false], vec![false, true]]).unwrap();
         assert_eq!(
-            Paper::new(vec![vec![true, false], vec![false, true]]),
-            Ok(Paper {
-                storage: vec![vec![true, false], vec![false, true]],
-                width: 2,
-                height: 2
-            })
+            paper.dots,
+            HashSet::from([Vector::new([0, 0]), Vector::new([1, 1])])
         );
+        assert_eq!((paper.width, paper.height), (2, 2));
+        assert_eq!(paper.unfolded_dots, paper.dots);
+        assert!(paper.folds.is_empty());
     }
 
     #[test]
     fn test_vertical_fold_around_zero() {
         let mut paper = Paper::new(vec![
-            vec![false, true, false],
+            vec![false, false, false],
             vec![true, false, true],
             vec![true, true, false],
         ])
         .unwrap();
         let expected = vec![vec![true, true, false], vec![true, false, true]];
-        paper.fold_vertically(0);
-        assert_eq!(paper.storage, expected);
+        paper.fold_vertically(0).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 3);
         assert_eq!(paper.height, 2);
     }
@@ -223,12 +294,12 @@ mod tests {
         let mut paper = Paper::new(vec![
             vec![false, true, false],
             vec![true, false, true],
-            vec![true, true, false],
+            vec![false, false, false],
         ])
         .unwrap();
         let expected = vec![vec![false, true, false], vec![true, false, true]];
-        paper.fold_vertically(2);
-        assert_eq!(paper.storage, expected);
+        paper.fold_vertically(2).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 3);
         assert_eq!(paper.height, 2);
     }
@@ -238,7 +309,7 @@ mod tests {
         let mut paper = Paper::new(vec![
             vec![true, false, true, false, false, true],
             vec![true, true, true, false, false, true],
-            vec![false, false, true, false, true, false],
+            vec![false, false, false, false, false, false],
             vec![false, false, false, true, true, false],
             vec![false, true, true, false, false, false],
         ])
@@ -247,8 +318,8 @@ mod tests {
             vec![true, true, true, false, false, true],
             vec![true, true, true, true, true, true],
         ];
-        paper.fold_vertically(2);
-        assert_eq!(paper.storage, expected);
+        paper.fold_vertically(2).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 6);
         assert_eq!(paper.height, 2);
     }
@@ -257,7 +328,7 @@ mod tests {
     fn test_vertical_fold_top_smaller() {
         let mut paper = Paper::new(vec![
             vec![true, false, true, false, false, true],
-            vec![true, true, true, false, false, true],
+            vec![false, false, false, false, false, false],
             vec![false, false, true, false, true, false],
             vec![false, false, false, true, true, false],
             vec![false, true, true, false, false, false],
@@ -268,8 +339,8 @@ mod tests {
             vec![false, false, false, true, true, false],
             vec![true, false, true, false, true, true],
         ];
-        paper.fold_vertically(1);
-        assert_eq!(paper.storage, expected);
+        paper.fold_vertically(1).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 6);
         assert_eq!(paper.height, 3);
     }
@@ -280,7 +351,7 @@ mod tests {
             vec![true, false, true, false, false, true],
             vec![true, true, true, false, false, true],
             vec![false, false, true, false, true, false],
-            vec![false, false, false, true, true, false],
+            vec![false, false, false, false, false, false],
             vec![false, true, true, false, false, false],
         ])
         .unwrap();
@@ -289,8 +360,8 @@ mod tests {
             vec![true, true, true, false, false, true],
             vec![false, true, true, false, true, false],
         ];
-        paper.fold_vertically(3);
-        assert_eq!(paper.storage, expected);
+        paper.fold_vertically(3).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 6);
         assert_eq!(paper.height, 3);
     }
@@ -298,8 +369,8 @@ mod tests {
     #[test]
     fn test_horizontal_fold_around_left() {
         let mut paper = Paper::new(vec![
-            vec![true, false, true, false, false, true],
-            vec![true, true, true, false, false, true],
+            vec![false, false, true, false, false, true],
+            vec![false, true, true, false, false, true],
             vec![false, false, true, false, true, false],
             vec![false, false, false, true, true, false],
             vec![false, true, true, false, false, false],
@@ -312,8 +383,8 @@ mod tests {
             vec![false, true, true, false, false],
             vec![false, false, false, true, true],
         ];
-        paper.fold_horizontally(0);
-        assert_eq!(paper.storage, expected);
+        paper.fold_horizontally(0).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 5);
         assert_eq!(paper.height, 5);
     }
@@ -321,8 +392,8 @@ mod tests {
     #[test]
     fn test_horizontal_fold_around_right() {
         let mut paper = Paper::new(vec![
-            vec![true, false, true, false, false, true],
-            vec![true, true, true, false, false, true],
+            vec![true, false, true, false, false, false],
+            vec![true, true, true, false, false, false],
             vec![false, false, true, false, true, false],
             vec![false, false, false, true, true, false],
             vec![false, true, true, false, false, false],
@@ -335,8 +406,8 @@ mod tests {
             vec![false, false, false, true, true],
             vec![false, true, true, false, false],
         ];
-        paper.fold_horizontally(5);
-        assert_eq!(paper.storage, expected);
+        paper.fold_horizontally(5).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 5);
         assert_eq!(paper.height, 5);
     }
@@ -344,11 +415,11 @@ mod tests {
     #[test]
     fn test_horizontal_fold_around_middle() {
         let mut paper = Paper::new(vec![
-            vec![true, false, true, false, false],
-            vec![true, true, true, false, false],
-            vec![false, false, true, false, true],
+            vec![true, false, false, false, false],
+            vec![true, true, false, false, false],
+            vec![false, false, false, false, true],
             vec![false, false, false, true, true],
-            vec![false, true, true, false, false],
+            vec![false, true, false, false, false],
         ])
         .unwrap();
         let expected = vec![
@@ -358,8 +429,8 @@ mod tests {
             vec![true, true],
             vec![false, true],
         ];
-        paper.fold_horizontally(2);
-        assert_eq!(paper.storage, expected);
+        paper.fold_horizontally(2).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 2);
         assert_eq!(paper.height, 5);
     }
@@ -370,7 +441,7 @@ mod tests {
             vec![true, false, true, false, false],
             vec![true, true, true, false, false],
             vec![false, false, true, false, true],
-            vec![false, false, false, true, true],
+            vec![false, false, false, false, true],
             vec![false, true, true, false, false],
         ])
         .unwrap();
@@ -381,8 +452,8 @@ mod tests {
             vec![false, false, true],
             vec![false, true, true],
         ];
-        paper.fold_horizontally(3);
-        assert_eq!(paper.storage, expected);
+        paper.fold_horizontally(3).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 3);
         assert_eq!(paper.height, 5);
     }
@@ -391,10 +462,10 @@ mod tests {
     fn test_horizontal_fold_larger_right_side() {
         let mut paper = Paper::new(vec![
             vec![true, false, true, false, false],
-            vec![true, true, true, false, false],
+            vec![true, false, true, false, false],
             vec![false, false, true, false, true],
             vec![false, false, false, true, true],
-            vec![false, true, true, false, false],
+            vec![false, false, true, false, false],
         ])
         .unwrap();
         let expected = vec![
@@ -404,8 +475,8 @@ mod tests {
             vec![true, true, false],
             vec![false, false, true],
         ];
-        paper.fold_horizontally(1);
-        assert_eq!(paper.storage, expected);
+        paper.fold_horizontally(1).unwrap();
+        assert_eq!(grid(&paper), expected);
         assert_eq!(paper.width, 3);
         assert_eq!(paper.height, 5);
     }
@@ -423,6 +494,26 @@ mod tests {
         assert_eq!(paper.count_dots(), 11);
     }
 
+    #[test]
+    fn test_paper_pbm_round_trip() {
+        let paper = Paper::new(vec![vec![true, false, true], vec![false, false, true]]).unwrap();
+        let mut output = vec![];
+        paper.write_pbm(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "P1\n3 2\n1 0 1\n0 0 1\n");
+        let netpbm = output.parse::<NetpbmImage>().unwrap();
+        assert_eq!(Paper::from_bitmap(&netpbm), Ok(paper));
+    }
+
+    #[test]
+    fn test_paper_from_empty_bitmap() {
+        let netpbm = "P1\n0 0\n".parse::<NetpbmImage>().unwrap();
+        assert_eq!(
+            Paper::from_bitmap(&netpbm),
+            Err(BuildPaperError::InvalidHeight)
+        );
+    }
+
     #[test]
     fn test_paper_display() {
         let paper = Paper::new(vec![
@@ -436,4 +527,83 @@ mod tests {
         const EXPECTED: &str = "#.#..\n###..\n..#.#\n...##\n.##..\n";
         assert_eq!(format!("{}", paper), EXPECTED);
     }
+
+    #[test]
+    fn test_fold_log_and_unfold() {
+        let mut paper = example();
+        paper.fold(FoldInstruction::new(Axis::Y, 7)).unwrap();
+        assert_eq!(paper.count_dots(), 17);
+        paper.fold(FoldInstruction::new(Axis::X, 5)).unwrap();
+        assert_eq!(paper.count_dots(), 16);
+        assert_eq!(
+            paper.folds(),
+            &[
+                FoldInstruction::new(Axis::Y, 7),
+                FoldInstruction::new(Axis::X, 5)
+            ]
+        );
+
+        assert_eq!(paper.unfold(), Some(FoldInstruction::new(Axis::X, 5)));
+        assert_eq!((paper.width, paper.height, paper.count_dots()), (11, 7, 17));
+        assert_eq!(paper.unfold(), Some(FoldInstruction::new(Axis::Y, 7)));
+        assert_eq!(paper.unfold(), None);
+        assert_eq!(paper, example());
+    }
+
+    #[test]
+    fn test_origins() {
+        let mut paper = example();
+        paper.fold_vertically(7).unwrap();
+        paper.fold_horizontally(5).unwrap();
+        let origins = paper.origins();
+        assert_eq!(origins.len(), 16);
+        assert_eq!(origins.values().map(Vec::len).sum::<usize>(), 18);
+        assert_eq!(origins[&Vector::new([0, 0])], vec![Vector::new([0, 14])]);
+        assert_eq!(
+            origins[&Vector::new([2, 4])],
+            vec![Vector::new([8, 4]), Vector::new([8, 10])]
+        );
+        assert_eq!(
+            origins[&Vector::new([1, 4])],
+            vec![Vector::new([1, 10]), Vector::new([9, 10])]
+        );
+    }
+
+    #[test]
+    fn test_fold_line_out_of_range() {
+        let mut paper = example();
+        assert_eq!(
+            paper.fold_vertically(15),
+            Err(FoldPaperError::LineOutOfRange {
+                line: 15,
+                length: 15
+            })
+        );
+        assert_eq!(
+            paper.fold_horizontally(20),
+            Err(FoldPaperError::LineOutOfRange {
+                line: 20,
+                length: 11
+            })
+        );
+        assert_eq!(paper, example());
+    }
+
+    #[test]
+    fn test_fold_dot_on_line() {
+        let mut paper = example();
+        assert_eq!(
+            paper.fold_vertically(10),
+            Err(FoldPaperError::DotOnLine {
+                dot: Vector::new([1, 10])
+            })
+        );
+        assert_eq!(
+            paper.fold_horizontally(6),
+            Err(FoldPaperError::DotOnLine {
+                dot: Vector::new([6, 0])
+            })
+        );
+        assert_eq!(paper, example());
+    }
 }
@@ -1,51 +1,32 @@
-use {super::Paper, std::iter::repeat};
-
-/// Struct which accumulates the dots on a piece of transparent paper and makes sure
-/// that the grid remains rectangular. Can be consumed after adding coordinates to produce
-/// the final sheet of Paper.
+use {
+    super::{Dot, Paper},
+    crate::common::math::Vector,
+    std::collections::HashSet,
+};
+
+/// Struct which accumulates the dots on a piece of transparent paper, keeping track of how
+/// big the sheet needs to be to fit all of them. Can be consumed after adding coordinates to
+/// produce the final sheet of Paper.
 #[derive(Debug, Default, PartialEq)]
 pub struct PaperBuilder {
-    storage: Vec<Vec<bool>>,
+    dots: HashSet<Dot>,
     width: usize,
     height: usize,
 }
 
 impl PaperBuilder {
-    /// Expands the internal storage in a rectangular fashion and plots a point down
-    /// at the given coordinate.
+    /// Plots a dot down at the given coordinate, growing the sheet to fit it if needed.
     pub fn place_dot(&mut self, x: usize, y: usize) {
-        if self.width <= x {
-            self.expand_sheet_horizontally(x - self.width + 1);
-        }
-        if self.height <= y {
-            self.expand_sheet_vertically(y - self.height + 1);
-        }
-        self.storage[y][x] = true;
+        self.dots.insert(Vector::new([x, y]));
+        self.width = self.width.max(x + 1);
+        self.height = self.height.max(y + 1);
     }
 
     /// Consumes the builder and attempts to produce a sheet of Paper with the dots plotted
     /// as defined. The only possible way to fail the build is is place_dot was never called
     /// first. Since this is an expected error we just return Option<Paper> rather than Result<Paper>.
     pub fn build(self) -> Option<Paper> {
-        Paper::new(self.storage).ok()
-    }
-
-    /// Expands the sheet horizontally by the given amount. This just expands
-    /// each line of the paper by this amount of false entries, and records the
-    /// expanded width.
-    fn expand_sheet_horizontally(&mut self, amount: usize) {
-        self.storage.iter_mut().for_each(|line| {
-            line.extend(repeat(false).take(amount));
-        });
-        self.width += amount;
-    }
-
-    /// Expands the sheet vertically by the given amount. This just adds new lines
-    /// of falses to the vector of the current width length and records the new height.
-    fn expand_sheet_vertically(&mut self, amount: usize) {
-        let empty_line = repeat(false).take(self.width).collect::<Vec<_>>();
-        self.storage.extend(repeat(empty_line).take(amount));
-        self.height += amount;
+        Paper::from_dots(self.dots, self.width, self.height).ok()
     }
 }
 
@@ -53,92 +34,6 @@ impl PaperBuilder {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_expand_sheet_vertically_zero() {
-        let mut builder = PaperBuilder::default();
-        builder.expand_sheet_vertically(0);
-        assert_eq!(
-            builder,
-            PaperBuilder {
-                storage: vec![],
-                width: 0,
-                height: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_expand_sheet_vertically_non_zero() {
-        let mut builder = PaperBuilder::default();
-        builder.expand_sheet_vertically(2);
-        assert_eq!(
-            builder,
-            PaperBuilder {
-                storage: vec![vec![], vec![]],
-                width: 0,
-                height: 2
-            }
-        );
-    }
-
-    #[test]
-    fn test_expand_sheet_horizontally_zero() {
-        let mut builder = PaperBuilder::default();
-        builder.expand_sheet_horizontally(0);
-        assert_eq!(
-            builder,
-            PaperBuilder {
-                storage: vec![],
-                width: 0,
-                height: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_expand_sheet_horizontally_non_zero() {
-        let mut builder = PaperBuilder::default();
-        builder.expand_sheet_horizontally(2);
-        assert_eq!(
-            builder,
-            PaperBuilder {
-                storage: vec![],
-                width: 2,
-                height: 0
-            }
-        );
-    }
-
-    #[test]
-    fn test_expand_sheet_horizontally_then_vertically() {
-        let mut builder = PaperBuilder::default();
-        builder.expand_sheet_horizontally(2);
-        builder.expand_sheet_vertically(2);
-        assert_eq!(
-            builder,
-            PaperBuilder {
-                storage: vec![vec![false, false], vec![false, false]],
-                width: 2,
-                height: 2
-            }
-        );
-    }
-
-    #[test]
-    fn test_expand_sheet_vertically_then_horizontally() {
-        let mut builder = PaperBuilder::default();
-        builder.expand_sheet_vertically(2);
-        builder.expand_sheet_horizontally(2);
-        assert_eq!(
-            builder,
-            PaperBuilder {
-                storage: vec![vec![false, false], vec![false, false]],
-                width: 2,
-                height: 2
-            }
-        );
-    }
-
     #[test]
     fn test_place_dot_origin() {
         let mut builder = PaperBuilder::default();
@@ -146,7 +41,7 @@ mod tests {
         assert_eq!(
             builder,
             PaperBuilder {
-                storage: vec![vec![true]],
+                dots: HashSet::from([Vector::new([0, 0])]),
                 width: 1,
                 height: 1
             }
@@ -157,28 +52,14 @@ mod tests {
     fn test_place_dot_make_expand_horizontally() {
         let mut builder = PaperBuilder::default();
         builder.place_dot(3, 0);
-        assert_eq!(
-            builder,
-            PaperBuilder {
-                storage: vec![vec![false, false, false, true]],
-                width: 4,
-                height: 1
-            }
-        );
+        assert_eq!((builder.width, builder.height), (4, 1));
     }
 
     #[test]
     fn test_place_dot_make_expand_vertically() {
         let mut builder = PaperBuilder::default();
         builder.place_dot(0, 3);
-        assert_eq!(
-            builder,
-            PaperBuilder {
-                storage: vec![vec![false], vec![false], vec![false], vec![true]],
-                width: 1,
-                height: 4
-            }
-        );
+        assert_eq!((builder.width, builder.height), (1, 4));
     }
 
     #[test]
@@ -189,11 +70,7 @@ mod tests {
         assert_eq!(
             builder,
             PaperBuilder {
-                storage: vec![
-                    vec![false, false, false, false],
-                    vec![false, true, false, false],
-                    vec![false, false, false, true]
-                ],
+                dots: HashSet::from([Vector::new([1, 1]), Vector::new([3, 2])]),
                 width: 4,
                 height: 3
             }
@@ -205,18 +82,16 @@ mod tests {
         let mut builder = PaperBuilder::default();
         builder.place_dot(3, 2);
         builder.place_dot(1, 1);
-        assert_eq!(
-            builder,
-            PaperBuilder {
-                storage: vec![
-                    vec![false, false, false, false],
-                    vec![false, true, false, false],
-                    vec![false, false, false, true]
-                ],
-                width: 4,
-                height: 3
-            }
-        );
+        assert_eq!((builder.width, builder.height), (4, 3));
+        assert_eq!(builder.dots.len(), 2);
+    }
+
+    #[test]
+    fn test_place_same_dot_twice() {
+        let mut builder = PaperBuilder::default();
+        builder.place_dot(2, 2);
+        builder.place_dot(2, 2);
+        assert_eq!(builder.dots.len(), 1);
     }
 
     #[test]
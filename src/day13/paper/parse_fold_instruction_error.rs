@@ -0,0 +1,33 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result},
+    num::ParseIntError,
+};
+
+/// Error cases for parsing a fold instruction such as "fold along x=5".
+#[derive(Debug, PartialEq)]
+pub enum ParseFoldInstructionError {
+    MissingPrefix,
+    MissingEquals,
+    InvalidAxis { axis: String },
+    ParseLineError(ParseIntError),
+}
+
+impl Display for ParseFoldInstructionError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::MissingPrefix => write!(f, "ParseFoldInstructionError::MissingPrefix"),
+            Self::MissingEquals => write!(f, "ParseFoldInstructionError::MissingEquals"),
+            Self::InvalidAxis { axis } => write!(
+                f,
+                "ParseFoldInstructionError::InvalidAxis {{ axis: {} }}",
+                axis
+            ),
+            Self::ParseLineError(error) => {
+                write!(f, "ParseFoldInstructionError::ParseLineError({})", error)
+            }
+        }
+    }
+}
+
+impl Error for ParseFoldInstructionError {}
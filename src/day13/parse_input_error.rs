@@ -0,0 +1,46 @@
+use {
+    super::ParseFoldInstructionError,
+    crate::common::math::ParseVectorError,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter, Result},
+        num::ParseIntError,
+    },
+};
+
+/// Error cases for parsing the puzzle input, where line is the (1 based) number of the line
+/// in the input that the error was found on.
+#[derive(Debug, PartialEq)]
+pub enum ParseInputError {
+    InvalidDot {
+        line: usize,
+        error: ParseVectorError<ParseIntError>,
+    },
+    InvalidFold {
+        line: usize,
+        error: ParseFoldInstructionError,
+    },
+    NoDots,
+    NoFolds,
+}
+
+impl Display for ParseInputError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::InvalidDot { line, error } => write!(
+                f,
+                "ParseInputError::InvalidDot {{ line: {}, error: {} }}",
+                line, error
+            ),
+            Self::InvalidFold { line, error } => write!(
+                f,
+                "ParseInputError::InvalidFold {{ line: {}, error: {} }}",
+                line, error
+            ),
+            Self::NoDots => write!(f, "ParseInputError::NoDots"),
+            Self::NoFolds => write!(f, "ParseInputError::NoFolds"),
+        }
+    }
+}
+
+impl Error for ParseInputError {}
@@ -1,14 +1,16 @@
+use crate::common::math::Vector;
+
 pub fn run(input: &str) -> (u32, usize) {
     let aabb = extract_aabb(input);
-    let y_velocity = calculate_starting_y_velocity_required_for_highest_peak(aabb.bottom_left.1);
+    let y_velocity = calculate_starting_y_velocity_required_for_highest_peak(aabb.bottom_left.y());
     let part_1 = calculate_triangular_number(y_velocity as u32);
     let velocity_space = calculate_velocity_space(&aabb);
     let part_2 = generate_velocities(velocity_space.clone())
         .filter(|velocity| {
             generate_positions_from_velocity(*velocity)
                 .take_while(|position| {
-                    position.0 <= velocity_space.top_right.0
-                        && position.1 >= velocity_space.bottom_left.1
+                    position.x() <= velocity_space.top_right.x()
+                        && position.y() >= velocity_space.bottom_left.y()
                 })
                 .filter(|position| is_position_inside_aabb(*position, &aabb))
                 .next()
@@ -22,40 +24,41 @@ pub fn run(input: &str) -> (u32, usize) {
 /// we're trying to land the probe inside of.
 #[derive(Clone, Debug, PartialEq)]
 struct AABB {
-    bottom_left: (i32, i32),
-    top_right: (i32, i32),
+    bottom_left: Vector<i32, 2>,
+    top_right: Vector<i32, 2>,
 }
 
 /// Generates all positions that a given velocity will have.
 /// Note that this generates an infinite sequence so it's upto calling code to
 /// clamp this.
-fn generate_positions_from_velocity(velocity: (i32, i32)) -> impl Iterator<Item = (i32, i32)> {
-    (0..).scan((velocity, (0, 0)), |velocity_and_position, _| {
-        let (velocity, position) = (&mut velocity_and_position.0, &mut velocity_and_position.1);
-        position.0 += velocity.0;
-        position.1 += velocity.1;
-        if velocity.0 > 0 {
-            velocity.0 -= 1;
-        } else if velocity.0 < 0 {
-            velocity.0 += 1;
-        }
-        velocity.1 -= 1;
-        Some(*position)
-    })
+fn generate_positions_from_velocity(
+    velocity: Vector<i32, 2>,
+) -> impl Iterator<Item = Vector<i32, 2>> {
+    (0..).scan(
+        (velocity, Vector::new([0, 0])),
+        |velocity_and_position, _| {
+            let (velocity, position) = (&mut velocity_and_position.0, &mut velocity_and_position.1);
+            *position += velocity;
+            // drag pulls x towards 0 while gravity always pulls y down.
+            *velocity -= &Vector::new([velocity.x().signum(), 1]);
+            Some(*position)
+        },
+    )
 }
 
 /// Tests whether a position is inside the given AABB or not.
-fn is_position_inside_aabb(position: (i32, i32), aabb: &AABB) -> bool {
-    position.0 >= aabb.bottom_left.0
-        && position.0 <= aabb.top_right.0
-        && position.1 >= aabb.bottom_left.1
-        && position.1 <= aabb.top_right.1
+fn is_position_inside_aabb(position: Vector<i32, 2>, aabb: &AABB) -> bool {
+    position.x() >= aabb.bottom_left.x()
+        && position.x() <= aabb.top_right.x()
+        && position.y() >= aabb.bottom_left.y()
+        && position.y() <= aabb.top_right.y()
 }
 
 /// Generates all velocities in the given velocity space.
-fn generate_velocities(velocity_space: AABB) -> impl Iterator<Item = (i32, i32)> {
-    (velocity_space.bottom_left.1..=velocity_space.top_right.1).flat_map(move |y| {
-        (velocity_space.bottom_left.0..=velocity_space.top_right.0).map(move |x| (x, y))
+fn generate_velocities(velocity_space: AABB) -> impl Iterator<Item = Vector<i32, 2>> {
+    (velocity_space.bottom_left.y()..=velocity_space.top_right.y()).flat_map(move |y| {
+        (velocity_space.bottom_left.x()..=velocity_space.top_right.x())
+            .map(move |x| Vector::new([x, y]))
     })
 }
 
@@ -68,13 +71,13 @@ fn calculate_velocity_space(trench: &AABB) -> AABB {
     // we don't start x at 0, but instead constrain to x >= n where n is the
     // highest triangular number that's less than the trench edge.
     let x_min = (1..)
-        .take_while(|i| calculate_triangular_number(*i) as i32 <= trench.bottom_left.0)
+        .take_while(|i| calculate_triangular_number(*i) as i32 <= trench.bottom_left.x())
         .last()
         .unwrap() as i32;
 
     AABB {
-        bottom_left: (x_min, trench.bottom_left.1),
-        top_right: (trench.top_right.0, -(trench.bottom_left.1 + 1)),
+        bottom_left: Vector::new([x_min, trench.bottom_left.y()]),
+        top_right: Vector::new([trench.top_right.x(), -(trench.bottom_left.y() + 1)]),
     }
 }
 
@@ -90,8 +93,8 @@ fn extract_aabb(input: &str) -> AABB {
     let (x_min, x_max) = parse_range(input.next().unwrap());
     let (y_min, y_max) = parse_range(input.next().unwrap());
     AABB {
-        bottom_left: (x_min, y_min),
-        top_right: (x_max, y_max),
+        bottom_left: Vector::new([x_min, y_min]),
+        top_right: Vector::new([x_max, y_max]),
     }
 }
 
@@ -153,8 +156,8 @@ mod tests {
         assert_eq!(
             extract_aabb("target area: x=20..30, y=-10..-5"),
             AABB {
-                bottom_left: (20, -10),
-                top_right: (30, -5)
+                bottom_left: Vector::new([20, -10]),
+                top_right: Vector::new([30, -5])
             }
         );
     }
@@ -172,13 +175,13 @@ mod tests {
     #[test]
     fn test_calculate_velocity_space() {
         let trench = AABB {
-            bottom_left: (22, -30),
-            top_right: (55, -10),
+            bottom_left: Vector::new([22, -30]),
+            top_right: Vector::new([55, -10]),
         };
         let velocity_space = calculate_velocity_space(&trench);
         const EXPECTED: AABB = AABB {
-            bottom_left: (6, -30),
-            top_right: (55, 29),
+            bottom_left: Vector::new([6, -30]),
+            top_right: Vector::new([55, 29]),
         };
         assert_eq!(velocity_space, EXPECTED);
     }
@@ -186,8 +189,8 @@ mod tests {
     #[test]
     fn test_generate_velocities() {
         const VELOCITY_SPACE: AABB = AABB {
-            bottom_left: (0, -3),
-            top_right: (3, 3),
+            bottom_left: Vector::new([0, -3]),
+            top_right: Vector::new([3, 3]),
         };
         let expected = vec![
             (0, -3),
@@ -218,7 +221,10 @@ mod tests {
             (1, 3),
             (2, 3),
             (3, 3),
-        ];
+        ]
+        .into_iter()
+        .map(|(x, y)| Vector::new([x, y]))
+        .collect::<Vec<_>>();
         assert_eq!(
             generate_velocities(VELOCITY_SPACE).collect::<Vec<_>>(),
             expected
@@ -227,7 +233,7 @@ mod tests {
 
     #[test]
     fn test_generate_positions_from_velocity() {
-        let velocity = (3, 6);
+        let velocity = Vector::new([3, 6]);
         let calculated = generate_positions_from_velocity(velocity)
             .take(10)
             .collect::<Vec<_>>();
@@ -242,19 +248,22 @@ mod tests {
             (6, 20),
             (6, 18),
             (6, 15),
-        ];
+        ]
+        .into_iter()
+        .map(|(x, y)| Vector::new([x, y]))
+        .collect::<Vec<_>>();
         assert_eq!(calculated, expected);
     }
 
     #[test]
     fn test_is_position_inside_aabb() {
         const BOUNDS: AABB = AABB {
-            bottom_left: (10, -20),
-            top_right: (30, -5),
+            bottom_left: Vector::new([10, -20]),
+            top_right: Vector::new([30, -5]),
         };
-        assert!(is_position_inside_aabb((13, -16), &BOUNDS));
-        assert!(!is_position_inside_aabb((7, -16), &BOUNDS));
-        assert!(!is_position_inside_aabb((13, -3), &BOUNDS));
+        assert!(is_position_inside_aabb(Vector::new([13, -16]), &BOUNDS));
+        assert!(!is_position_inside_aabb(Vector::new([7, -16]), &BOUNDS));
+        assert!(!is_position_inside_aabb(Vector::new([13, -3]), &BOUNDS));
     }
 
     #[test]
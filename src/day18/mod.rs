@@ -1,14 +1,16 @@
 mod number;
 
-use number::Number;
+use {
+    crate::common::collections::Stack,
+    number::{Number, StackEntry},
+    std::{fmt::Display, thread},
+};
+
+pub use number::ParseSnailfishError;
 
 pub fn run(input: &str) -> (u64, u64) {
     let mut stack = vec![];
-    let numbers = input
-        .trim()
-        .lines()
-        .map(|line| line.trim().parse::<Number>().unwrap())
-        .collect::<Vec<_>>();
+    let numbers = parse_numbers(input).unwrap();
     let part_1 = numbers
         .iter()
         .fold(numbers[0].clone(), |total, number| {
@@ -17,21 +19,155 @@ pub fn run(input: &str) -> (u64, u64) {
             total
         })
         .magnitude();
-    let part_2 = numbers
-        .iter()
-        .enumerate()
-        .flat_map(|(i, number_i)| {
+    let part_2 = largest_pairwise_magnitude(&numbers, 0..numbers.len(), &mut stack).unwrap();
+    (part_1, part_2)
+}
+
+/// Finds the largest magnitude from adding any two different numbers from the input, in the
+/// same way as part 2 of run, but with the search split across the given number of threads.
+/// Snailfish numbers can't be shared between threads so each worker parses its own copy of the
+/// input and has its own scratch stack. Worker n takes every nth number as the left hand side of
+/// the additions it checks. Asking for zero threads is treated the same as asking for one.
+pub fn largest_pairwise_magnitude_parallel(
+    input: &str,
+    threads: usize,
+) -> Result<Option<u64>, ParseSnailfishError> {
+    let threads = threads.max(1);
+    thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|worker| {
+                scope.spawn(move || {
+                    let numbers = parse_numbers(input)?;
+                    let firsts = (worker..numbers.len()).step_by(threads);
+                    Ok(largest_pairwise_magnitude(&numbers, firsts, &mut vec![]))
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap())
+            .try_fold(None, |largest, magnitude| Ok(largest.max(magnitude?)))
+    })
+}
+
+/// Parses each non-empty line of the input as a snailfish number.
+fn parse_numbers(input: &str) -> Result<Vec<Number>, ParseSnailfishError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| line.trim().parse::<Number>())
+        .collect()
+}
+
+/// Adds each of the numbers at the given indices to every other number in the slice and
+/// returns the largest magnitude found after reducing, or None if no additions were made.
+fn largest_pairwise_magnitude(
+    numbers: &[Number],
+    firsts: impl Iterator<Item = usize>,
+    stack: &mut impl Stack<StackEntry>,
+) -> Option<u64> {
+    firsts
+        .flat_map(|i| {
             numbers
                 .iter()
                 .take(i)
                 .chain(numbers.iter().skip(i + 1))
-                .map(|number_j| number_i.clone() + number_j.clone())
+                .map(move |number_j| numbers[i].clone() + number_j.clone())
         })
         .map(|number| {
-            number.reduce(&mut stack);
+            number.reduce(stack);
             number.magnitude()
         })
         .max()
+}
+
+/// Adds the two given snailfish numbers and fully reduces the result, calling the provided
+/// function with a label and the state of the number for the addition and then for every
+/// explode and split which is applied during the reduction.
+pub fn trace_addition(
+    left: &str,
+    right: &str,
+    mut func: impl FnMut(&dyn Display, &dyn Display),
+) -> Result<(), ParseSnailfishError> {
+    let total = left.trim().parse::<Number>()? + right.trim().parse::<Number>()?;
+    func(&"after addition", &total);
+    total.reduce_with_observer(&mut vec![], |reduction, number| func(reduction, number));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+    #[test]
+    fn test_largest_pairwise_magnitude() {
+        let numbers = parse_numbers(EXAMPLE).unwrap();
+        assert_eq!(
+            largest_pairwise_magnitude(&numbers, 0..numbers.len(), &mut vec![]),
+            Some(3993)
+        );
+    }
+
+    #[test]
+    fn test_largest_pairwise_magnitude_parallel() {
+        (0..=12).for_each(|threads| {
+            assert_eq!(
+                largest_pairwise_magnitude_parallel(EXAMPLE, threads),
+                Ok(Some(3993))
+            );
+        });
+    }
+
+    #[test]
+    fn test_largest_pairwise_magnitude_parallel_single_number() {
+        assert_eq!(largest_pairwise_magnitude_parallel("[1,2]", 4), Ok(None));
+    }
+
+    #[test]
+    fn test_largest_pairwise_magnitude_parallel_parse_error() {
+        assert_eq!(
+            largest_pairwise_magnitude_parallel("[1,2]\n[1,x]", 2),
+            Err(ParseSnailfishError::UnexpectedCharacter {
+                position: 3,
+                found: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_trace_addition() {
+        let mut lines = vec![];
+        trace_addition("[[[[4,3],4],4],[7,[[8,4],9]]]", "[1,1]", |label, number| {
+            lines.push(format!("{}: {}", label, number))
+        })
         .unwrap();
-    (part_1, part_2)
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[0],
+            "after addition: [[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]"
+        );
+        assert_eq!(
+            lines[5],
+            "explode at depth 4 (LRRR): [[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+        );
+    }
+
+    #[test]
+    fn test_trace_addition_parse_error() {
+        assert_eq!(
+            trace_addition("[1,2]", "[1,", |_, _| {}),
+            Err(ParseSnailfishError::UnexpectedEnd { position: 3 })
+        );
+    }
 }
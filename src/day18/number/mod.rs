@@ -1,14 +1,29 @@
 mod number_type;
+mod parse_snailfish_error;
+mod path;
+mod reduction;
 mod stack_entry;
 
+pub use {
+    parse_snailfish_error::ParseSnailfishError,
+    path::{Path, Turn},
+    reduction::Reduction,
+    stack_entry::StackEntry,
+};
+
 use {
     crate::common::collections::Stack,
     number_type::{
         contains_number_type, lowest_literal_down_left_branch, lowest_literal_down_right_branch,
         NumberType,
     },
-    stack_entry::StackEntry,
-    std::{cell::RefCell, convert::Infallible, ops::Add, rc::Rc, str::FromStr},
+    std::{
+        cell::RefCell,
+        fmt::{self, Display, Formatter},
+        ops::Add,
+        rc::Rc,
+        str::FromStr,
+    },
 };
 
 /// Definition of a "Snailfish Number" which is represented as a pair
@@ -21,7 +36,24 @@ impl Number {
     /// Reduces the number entirely by repeated application of explode and split.
     /// reduced once both explode and split return false.
     pub fn reduce(&self, stack: &mut impl Stack<StackEntry>) {
-        while self.try_explode(stack) || self.try_split(stack) {}
+        self.reduce_with_observer(stack, |_, _| {});
+    }
+
+    /// Reduces the number in the same way as reduce, but calls the observer after each
+    /// explode or split has been applied with the action that was taken and the state of
+    /// the number after it.
+    pub fn reduce_with_observer(
+        &self,
+        stack: &mut impl Stack<StackEntry>,
+        mut observer: impl FnMut(&Reduction, &Self),
+    ) {
+        while let Some(reduction) = self
+            .try_explode(stack)
+            .map(Reduction::Explode)
+            .or_else(|| self.try_split(stack).map(Reduction::Split))
+        {
+            observer(&reduction, self);
+        }
     }
 
     /// Determines the magnitude of the snailfish number.
@@ -31,39 +63,40 @@ impl Number {
 
     /// Navigates the number structure to see if any pair needs to explode. If it does
     /// will perform the explosion and return true. If this function returns false then
-    /// no explosion happened (and we should try a split). Uses a preallocated stack to
+    /// no explosion happened (and we should try a split), otherwise returns the path to the
+    /// pair that exploded. Uses a preallocated stack to
     /// track the parentage of the explosion. When we're popping off the stack, the left
     /// value will be added to the leftmost regular number of the parent pairs, and the
     /// right value will be added to the rightmost regular number of the parent pairs.
-    fn try_explode(&self, stack: &mut impl Stack<StackEntry>) -> bool {
+    fn try_explode(&self, stack: &mut impl Stack<StackEntry>) -> Option<Path> {
         self.find_explosion(stack);
         if let Some(entry) = stack.pop() {
             let exploded = entry.unwrap();
-            self.apply_explosion(stack, &exploded);
+            let path = self.apply_explosion(stack, &exploded);
             *exploded.borrow_mut() = NumberType::Regular(0); // replace exploded pair with just the number 0
-            true
+            Some(path)
         } else {
-            false
+            None
         }
     }
 
     /// Tries to find a regular number in the structure which requires splitting. If it
-    /// finds one, it will perform the split and then return true. If this function returns
-    /// false then no split happened. Uses a preallocated stack to track what has been explored yet.
+    /// finds one, it will perform the split and then return the path to the split number. If this
+    /// function returns None then no split happened. Uses a preallocated stack to track what has been explored yet.
     /// More or less the same as explode, except the application is (a LOT) easier as it's just swapping
     /// the element in place from Regular to a pair. The API is different from find_split, since we don't require
     /// the full history kept in the stack like with explode, find_split will wipe the stack and return just the
     /// split entry.
-    fn try_split(&self, stack: &mut impl Stack<StackEntry>) -> bool {
-        if let Some(split) = self.find_split(stack) {
+    fn try_split(&self, stack: &mut impl Stack<StackEntry>) -> Option<Path> {
+        if let Some((split, path)) = self.find_split(stack) {
             let value = split.borrow().extract_regular().unwrap() as f32 / 2.0;
             let floor = NumberType::Regular(value.floor() as u8);
             let ceil = NumberType::Regular(value.ceil() as u8);
             *split.borrow_mut() =
                 NumberType::Pair(Rc::new(RefCell::new(floor)), Rc::new(RefCell::new(ceil)));
-            true
+            Some(path)
         } else {
-            false
+            None
         }
     }
 
@@ -71,12 +104,13 @@ impl Number {
     /// to be exploded. In order to explode, we need to find the "next left" and "next right" regular
     /// numbers. We'd have to do this by moving up the stack one level at a time and traversing down the
     /// left/right branches (ignoring the left/right items themselves) to find out if it has an entry or not.
-    /// The next left item is actually traversing the right branch of the parent
+    /// The next left item is actually traversing the right branch of the parent. Since we pop
+    /// the whole backtrace, we also record the path to the exploded pair while doing so.
     fn apply_explosion(
         &self,
         stack: &mut impl Stack<StackEntry>,
         exploded: &Rc<RefCell<NumberType>>,
-    ) {
+    ) -> Path {
         // This is safe because we are expecting that the exploded NumberType is a pair (otherwise it wouldn't have exploded).
         let (left, right) = exploded.borrow().decompose_pair().unwrap();
 
@@ -84,10 +118,12 @@ impl Number {
         // the stack.
         let mut left_target: Option<Rc<RefCell<NumberType>>> = None;
         let mut right_target: Option<Rc<RefCell<NumberType>>> = None;
+        let mut path = Path::with_depth(stack.len());
 
         // Traverse up the stack to both clear it and also to find the targets to apply
         // the addition to.
         while let Some(entry) = stack.pop() {
+            path.set_turn(stack.len(), entry.turn());
             if left_target.is_none() || right_target.is_none() {
                 // This is safe because we only ever have a backtrace of pairs on the stack after an explosion.
                 let (left, right) = entry.unwrap().borrow().decompose_pair().unwrap();
@@ -119,6 +155,8 @@ impl Number {
                 + right.borrow().extract_regular().unwrap();
             *right_target.borrow_mut() = NumberType::Regular(total);
         }
+
+        path
     }
 
     /// Attempts to find an explosion in the number. Populates the stack with the route to the
@@ -152,16 +190,22 @@ impl Number {
 
     /// Similar to find_explosion except instead of looking for a deeply nested pair, looks for a
     /// literal greater or equal to 10 instead. Additionally, the stack is only needed for tracking
-    /// recursion and is wiped out when we return.
-    fn find_split(&self, stack: &mut impl Stack<StackEntry>) -> Option<Rc<RefCell<NumberType>>> {
+    /// recursion and is wiped out when we return, recording the path to the literal as it goes.
+    fn find_split(
+        &self,
+        stack: &mut impl Stack<StackEntry>,
+    ) -> Option<(Rc<RefCell<NumberType>>, Path)> {
         stack.push(StackEntry::NotTraversed(self.0.clone()));
         while let Some(entry) = stack.pop() {
             match entry {
                 StackEntry::NotTraversed(ref ptr) => {
                     if let Some(value) = ptr.borrow().extract_regular() {
                         if value >= 10 {
-                            while let Some(_) = stack.pop() {} // drain rest of stack.
-                            return Some((*ptr).clone());
+                            let mut path = Path::with_depth(stack.len());
+                            while let Some(entry) = stack.pop() {
+                                path.set_turn(stack.len(), entry.turn()); // drain rest of stack.
+                            }
+                            return Some(((*ptr).clone(), path));
                         }
                     } else if let Some((first, _)) = ptr.borrow().decompose_pair() {
                         stack.push(StackEntry::TraversedLeft((*ptr).clone()));
@@ -199,13 +243,20 @@ impl Clone for Number {
     }
 }
 
+impl Display for Number {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.borrow().fmt(f)
+    }
+}
+
 impl FromStr for Number {
-    type Err = Infallible;
+    type Err = ParseSnailfishError;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        Ok(Self(Rc::new(RefCell::new(
-            string.parse::<NumberType>().unwrap(),
-        ))))
+        match string.parse::<NumberType>()? {
+            NumberType::Regular(..) => Err(ParseSnailfishError::NotAPair),
+            pair => Ok(Self(Rc::new(RefCell::new(pair)))),
+        }
     }
 }
 
@@ -235,7 +286,7 @@ mod tests {
         let mut stack = Vec::with_capacity(5);
         let number = "[7,[6,[5,[4,[3,2]]]]]".parse::<Number>().unwrap();
         let expected = "[7,[6,[5,[7,0]]]]".parse::<Number>().unwrap();
-        assert!(number.try_explode(&mut stack));
+        assert!(number.try_explode(&mut stack).is_some());
         assert!(stack.is_empty());
         assert_eq!(number, expected);
     }
@@ -245,7 +296,7 @@ mod tests {
         let mut stack = Vec::with_capacity(5);
         let number = "[[6,[5,[4,[3,2]]]],1]".parse::<Number>().unwrap();
         let expected = "[[6,[5,[7,0]]],3]".parse::<Number>().unwrap();
-        assert!(number.try_explode(&mut stack));
+        assert!(number.try_explode(&mut stack).is_some());
         assert!(stack.is_empty());
         assert_eq!(number, expected);
     }
@@ -259,7 +310,7 @@ mod tests {
         let expected = "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"
             .parse::<Number>()
             .unwrap();
-        assert!(number.try_explode(&mut stack));
+        assert!(number.try_explode(&mut stack).is_some());
         assert!(stack.is_empty());
         assert_eq!(number, expected);
     }
@@ -271,7 +322,7 @@ mod tests {
             .parse::<Number>()
             .unwrap();
         let expected = "[[3,[2,[8,0]]],[9,[5,[7,0]]]]".parse::<Number>().unwrap();
-        assert!(number.try_explode(&mut stack));
+        assert!(number.try_explode(&mut stack).is_some());
         assert!(stack.is_empty());
         assert_eq!(number, expected);
     }
@@ -281,7 +332,7 @@ mod tests {
         let mut stack = Vec::with_capacity(5);
         let number = Number(Rc::new(RefCell::new(NumberType::Regular(10))));
         let expected = "[5,5]".parse::<Number>().unwrap();
-        assert!(number.try_split(&mut stack));
+        assert!(number.try_split(&mut stack).is_some());
         assert!(stack.is_empty());
         assert_eq!(number, expected);
     }
@@ -291,7 +342,7 @@ mod tests {
         let mut stack = Vec::with_capacity(5);
         let number = Number(Rc::new(RefCell::new(NumberType::Regular(11))));
         let expected = "[5,6]".parse::<Number>().unwrap();
-        assert!(number.try_split(&mut stack));
+        assert!(number.try_split(&mut stack).is_some());
         assert!(stack.is_empty());
         assert_eq!(number, expected);
     }
@@ -301,7 +352,7 @@ mod tests {
         let mut stack = Vec::with_capacity(5);
         let number = Number(Rc::new(RefCell::new(NumberType::Regular(12))));
         let expected = "[6,6]".parse::<Number>().unwrap();
-        assert!(number.try_split(&mut stack));
+        assert!(number.try_split(&mut stack).is_some());
         assert!(stack.is_empty());
         assert_eq!(number, expected);
     }
@@ -318,4 +369,87 @@ mod tests {
             .unwrap();
         assert_eq!(total, expected);
     }
+
+    #[test]
+    fn test_try_explode_path() {
+        let mut stack = Vec::with_capacity(5);
+        let number = "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]"
+            .parse::<Number>()
+            .unwrap();
+        assert_eq!(number.try_explode(&mut stack).unwrap().to_string(), "LRRR");
+    }
+
+    #[test]
+    fn test_try_split_path() {
+        let mut stack = Vec::with_capacity(5);
+        let number = "[[1,2],[3,4]]".parse::<Number>().unwrap();
+        *lowest_literal_down_left_branch(&number.0.borrow().decompose_pair().unwrap().1)
+            .borrow_mut() = NumberType::Regular(13);
+        assert_eq!(number.try_split(&mut stack).unwrap().to_string(), "RL");
+        assert!(stack.is_empty());
+        assert_eq!(number, "[[1,2],[[6,7],4]]".parse::<Number>().unwrap());
+    }
+
+    #[test]
+    fn test_reduce_with_observer() {
+        let first_num = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse::<Number>().unwrap();
+        let second_num = "[1,1]".parse::<Number>().unwrap();
+        let total = first_num + second_num;
+        let mut stack = Vec::with_capacity(5);
+        let mut steps = vec![];
+        total.reduce_with_observer(&mut stack, |reduction, number| {
+            steps.push(format!("{}: {}", reduction, number))
+        });
+        assert_eq!(
+            steps,
+            vec![
+                "explode at depth 4 (LLLL): [[[[0,7],4],[7,[[8,4],9]]],[1,1]]",
+                "explode at depth 4 (LRRL): [[[[0,7],4],[15,[0,13]]],[1,1]]",
+                "split at depth 3 (LRL): [[[[0,7],4],[[7,8],[0,13]]],[1,1]]",
+                "split at depth 4 (LRRR): [[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]",
+                "explode at depth 4 (LRRR): [[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_str_not_a_pair() {
+        assert_eq!("7".parse::<Number>(), Err(ParseSnailfishError::NotAPair));
+    }
+
+    #[test]
+    fn test_display() {
+        let number = "[[[[0, 7], 4], [[7, 8], [6, 0]]], [8, 1]]"
+            .parse::<Number>()
+            .unwrap();
+        assert_eq!(number.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+    }
+
+    /// Small xorshift generator so we can produce lots of arbitrary numbers for the
+    /// round trip property test without pulling in a crate.
+    fn next_random(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    fn random_number_type(state: &mut u64, depth: usize) -> NumberType {
+        if depth > 0 && next_random(state) % 3 < 2 {
+            random_number_type(state, depth - 1) + random_number_type(state, depth - 1)
+        } else {
+            NumberType::Regular((next_random(state) % 10) as u8)
+        }
+    }
+
+    #[test]
+    fn test_display_from_str_round_trip() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        (0..1000).for_each(|_| {
+            let number = Number(Rc::new(RefCell::new(
+                random_number_type(&mut state, 5) + random_number_type(&mut state, 5),
+            )));
+            assert_eq!(number.to_string().parse::<Number>().unwrap(), number);
+        });
+    }
 }
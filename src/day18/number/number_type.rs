@@ -1,4 +1,14 @@
-use std::{cell::RefCell, convert::Infallible, ops::Add, rc::Rc, str::FromStr};
+use {
+    super::parse_snailfish_error::ParseSnailfishError,
+    std::{
+        cell::RefCell,
+        fmt::{self, Display, Formatter},
+        iter::Peekable,
+        ops::Add,
+        rc::Rc,
+        str::{CharIndices, FromStr},
+    },
+};
 
 /// Represents the type of an element of a pair in Snailfish numbers.
 /// An element can either be another pair of NumberType or can be
@@ -58,30 +68,81 @@ impl Clone for NumberType {
     }
 }
 
+// Writes the number back out in the same format it's parsed from, e.g. [[1,2],3].
+impl Display for NumberType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Regular(value) => write!(f, "{}", value),
+            Self::Pair(p1, p2) => write!(f, "[{},{}]", p1.borrow(), p2.borrow()),
+        }
+    }
+}
+
 impl FromStr for NumberType {
-    type Err = Infallible;
+    type Err = ParseSnailfishError;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let mut stack = vec![]; // used to parse
-        string.trim().chars().for_each(|c| match c {
-            '[' | ',' | ' ' => return,
-            ']' => {
-                let second = stack.pop().unwrap();
-                let first = stack.pop().unwrap();
-                stack.push(Rc::new(RefCell::new(NumberType::Pair(first, second))));
+        let mut chars = string.char_indices().peekable();
+        let parsed = parse_element(&mut chars, string.len())?;
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some((position, found)) => {
+                Err(ParseSnailfishError::UnexpectedCharacter { position, found })
             }
-            _ => stack.push(Rc::new(RefCell::new(NumberType::Regular(
-                c.to_digit(10).unwrap() as u8,
-            )))),
-        });
-        let parsed = (*stack.pop().unwrap().borrow()).clone();
-        if stack.len() != 0 {
-            panic!("Invalid data format, parse stack isn't empty after parsing.")
+            None => Ok(parsed),
         }
-        Ok(parsed)
     }
 }
 
+/// Parses a single element (either a pair or a single digit regular number) from
+/// the character stream. Pairs are parsed recursively. The end position is needed so
+/// that running out of characters can be reported at the correct place.
+fn parse_element(
+    chars: &mut Peekable<CharIndices>,
+    end: usize,
+) -> Result<NumberType, ParseSnailfishError> {
+    match next_token(chars, end)? {
+        (_, '[') => {
+            let first = parse_element(chars, end)?;
+            expect_token(chars, end, ',')?;
+            let second = parse_element(chars, end)?;
+            expect_token(chars, end, ']')?;
+            Ok(first + second)
+        }
+        (position, found) => found
+            .to_digit(10)
+            .map(|digit| NumberType::Regular(digit as u8))
+            .ok_or(ParseSnailfishError::UnexpectedCharacter { position, found }),
+    }
+}
+
+/// Consumes the next non-whitespace character and checks that it's the one we expect.
+fn expect_token(
+    chars: &mut Peekable<CharIndices>,
+    end: usize,
+    expected: char,
+) -> Result<(), ParseSnailfishError> {
+    match next_token(chars, end)? {
+        (_, found) if found == expected => Ok(()),
+        (position, found) => Err(ParseSnailfishError::UnexpectedCharacter { position, found }),
+    }
+}
+
+/// Consumes and returns the next non-whitespace character along with its position.
+fn next_token(
+    chars: &mut Peekable<CharIndices>,
+    end: usize,
+) -> Result<(usize, char), ParseSnailfishError> {
+    skip_whitespace(chars);
+    chars
+        .next()
+        .ok_or(ParseSnailfishError::UnexpectedEnd { position: end })
+}
+
+fn skip_whitespace(chars: &mut Peekable<CharIndices>) {
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+}
+
 /// Extracts the lowest literal down the right branch.
 pub fn lowest_literal_down_right_branch(ptr: &Rc<RefCell<NumberType>>) -> Rc<RefCell<NumberType>> {
     match &*ptr.borrow() {
@@ -283,6 +344,55 @@ mod tests {
         let _ = "10".parse::<NumberType>().unwrap();
     }
 
+    #[test]
+    fn test_from_str_unexpected_character() {
+        assert_eq!(
+            "[1,x]".parse::<NumberType>(),
+            Err(ParseSnailfishError::UnexpectedCharacter {
+                position: 3,
+                found: 'x'
+            })
+        );
+        assert_eq!(
+            "[1;2]".parse::<NumberType>(),
+            Err(ParseSnailfishError::UnexpectedCharacter {
+                position: 2,
+                found: ';'
+            })
+        );
+        assert_eq!(
+            "[1,2]]".parse::<NumberType>(),
+            Err(ParseSnailfishError::UnexpectedCharacter {
+                position: 5,
+                found: ']'
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_str_unexpected_end() {
+        assert_eq!(
+            "[[1,2],".parse::<NumberType>(),
+            Err(ParseSnailfishError::UnexpectedEnd { position: 7 })
+        );
+        assert_eq!(
+            "".parse::<NumberType>(),
+            Err(ParseSnailfishError::UnexpectedEnd { position: 0 })
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NumberType::Regular(4).to_string(), "4");
+        assert_eq!(
+            "[[1, 2], [3, [4, 5]]]"
+                .parse::<NumberType>()
+                .unwrap()
+                .to_string(),
+            "[[1,2],[3,[4,5]]]"
+        );
+    }
+
     #[test]
     fn test_magnitude_calculation() {
         assert_eq!(
@@ -0,0 +1,33 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Error cases for parsing a snailfish number from a string. Positions are
+/// byte offsets into the string that was passed to the parser.
+#[derive(Debug, PartialEq)]
+pub enum ParseSnailfishError {
+    UnexpectedCharacter { position: usize, found: char },
+    UnexpectedEnd { position: usize },
+    NotAPair,
+}
+
+impl Display for ParseSnailfishError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::UnexpectedCharacter { position, found } => write!(
+                f,
+                "ParseSnailfishError::UnexpectedCharacter {{ position: {}, found: {:?} }}",
+                position, found
+            ),
+            Self::UnexpectedEnd { position } => write!(
+                f,
+                "ParseSnailfishError::UnexpectedEnd {{ position: {} }}",
+                position
+            ),
+            Self::NotAPair => write!(f, "ParseSnailfishError::NotAPair"),
+        }
+    }
+}
+
+impl Error for ParseSnailfishError {}
@@ -0,0 +1,91 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// A single step taken when moving from a pair down into one of its elements.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+/// The route from the root of a snailfish number down to one of its elements. The
+/// turns are stored as a bitmask (a set bit meaning right) so that tracking the path
+/// of every reduction step doesn't need to allocate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Path {
+    turns: u32,
+    depth: usize,
+}
+
+impl Path {
+    /// Constructs a new path of the given depth where every turn is to the left.
+    pub fn with_depth(depth: usize) -> Self {
+        Self { turns: 0, depth }
+    }
+
+    /// Returns how many pairs deep the element at the end of this path is nested.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Gets an iterator over the turns taken from the root down to the element.
+    pub fn turns(&self) -> impl Iterator<Item = Turn> + '_ {
+        (0..self.depth).map(|index| {
+            if self.turns & (1 << index) == 0 {
+                Turn::Left
+            } else {
+                Turn::Right
+            }
+        })
+    }
+
+    /// Sets the turn taken at the given depth of the path.
+    pub fn set_turn(&mut self, index: usize, turn: Turn) {
+        match turn {
+            Turn::Left => self.turns &= !(1 << index),
+            Turn::Right => self.turns |= 1 << index,
+        }
+    }
+}
+
+// Writes the path as a string of L and R characters, e.g. LLRL.
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        self.turns().try_for_each(|turn| match turn {
+            Turn::Left => write!(f, "L"),
+            Turn::Right => write!(f, "R"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_depth() {
+        let path = Path::with_depth(3);
+        assert_eq!(path.depth(), 3);
+        assert!(path.turns().all(|turn| turn == Turn::Left));
+    }
+
+    #[test]
+    fn test_set_turn() {
+        let mut path = Path::with_depth(4);
+        path.set_turn(1, Turn::Right);
+        path.set_turn(3, Turn::Right);
+        path.set_turn(3, Turn::Left);
+        assert_eq!(
+            path.turns().collect::<Vec<_>>(),
+            vec![Turn::Left, Turn::Right, Turn::Left, Turn::Left]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let mut path = Path::with_depth(4);
+        path.set_turn(0, Turn::Right);
+        path.set_turn(2, Turn::Right);
+        assert_eq!(path.to_string(), "RLRL");
+        assert_eq!(Path::default().to_string(), "");
+    }
+}
@@ -0,0 +1,21 @@
+use {
+    super::path::Path,
+    std::fmt::{Display, Formatter, Result},
+};
+
+/// A single action that was applied while reducing a snailfish number, along
+/// with the path to the element that the action was applied to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reduction {
+    Explode(Path),
+    Split(Path),
+}
+
+impl Display for Reduction {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Explode(path) => write!(f, "explode at depth {} ({})", path.depth(), path),
+            Self::Split(path) => write!(f, "split at depth {} ({})", path.depth(), path),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use {
-    super::NumberType,
+    super::{NumberType, Turn},
     std::{cell::RefCell, rc::Rc},
 };
 
@@ -33,4 +33,13 @@ impl StackEntry {
             Self::TraversedRight(ret) => ret,
         }
     }
+
+    /// Determines which branch of the pair has been (or is being) traversed. Entries that are
+    /// left on the stack as parents of another entry are always TraversedLeft or TraversedRight.
+    pub fn turn(&self) -> Turn {
+        match self {
+            Self::TraversedRight(..) => Turn::Right,
+            _ => Turn::Left,
+        }
+    }
 }
@@ -0,0 +1,13 @@
+/// How candidate sensor pairs and point correspondences are found when aligning sensors.
+/// Both strategies produce identical links, the brute force one is kept around as a
+/// reference to compare against.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AlignmentStrategy {
+    /// Compares every distance of one sensor with every distance of another, and then tries
+    /// every pairing of their points as the basis of a translation.
+    BruteForce,
+    /// Uses an index of the distances between points to find the sensors which could overlap
+    /// and which of their points could correspond, so that only those are tried.
+    #[default]
+    Fingerprint,
+}
@@ -0,0 +1,69 @@
+use crate::common::math::Vector;
+
+/// The metric used to measure the distances between each pair of beacons a sensor
+/// can see. These distances are compared between sensors to cheaply rule out pairs
+/// which can't overlap, so the metric needs to be unchanged by the rotations being tried.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DistanceMetric {
+    #[default]
+    Manhattan,
+    Chebyshev,
+    SquaredEuclidean,
+}
+
+impl DistanceMetric {
+    /// Calculates the distance between the two points using this metric.
+    pub fn distance(&self, p1: &Vector<i64, 3>, p2: &Vector<i64, 3>) -> i64 {
+        match self {
+            Self::Manhattan => p1.manhattan_distance(p2),
+            Self::Chebyshev => p1.chebyshev_distance(p2),
+            Self::SquaredEuclidean => p1.squared_euclidean_distance(p2),
+        }
+    }
+
+    /// Calculates how far the given distance between two points could change if each of them
+    /// moved by up to epsilon along every axis.
+    pub fn tolerance(&self, distance: i64, epsilon: i64) -> i64 {
+        match self {
+            Self::Manhattan => 6 * epsilon,
+            Self::Chebyshev => 2 * epsilon,
+            Self::SquaredEuclidean => {
+                let shift = 2.0 * 3.0_f64.sqrt() * epsilon as f64;
+                (2.0 * (distance as f64).sqrt() * shift + shift * shift).ceil() as i64
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        let p1 = Vector::new([1, -2, 3]);
+        let p2 = Vector::new([-3, 5, 4]);
+        assert_eq!(DistanceMetric::Manhattan.distance(&p1, &p2), 12);
+        assert_eq!(DistanceMetric::Chebyshev.distance(&p1, &p2), 7);
+        assert_eq!(DistanceMetric::SquaredEuclidean.distance(&p1, &p2), 66);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let p1 = Vector::new([1, -2, 3]);
+        let p2 = Vector::new([-3, 5, 4]);
+        let (q1, q2) = (Vector::new([2, -3, 4]), Vector::new([-4, 6, 3]));
+        [
+            DistanceMetric::Manhattan,
+            DistanceMetric::Chebyshev,
+            DistanceMetric::SquaredEuclidean,
+        ]
+        .into_iter()
+        .for_each(|metric| {
+            let distance = metric.distance(&p1, &p2);
+            let moved = metric.distance(&q1, &q2);
+            assert!((distance - moved).abs() <= metric.tolerance(distance, 1));
+            assert_eq!(metric.tolerance(distance, 0), 0);
+        });
+    }
+}
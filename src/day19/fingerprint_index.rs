@@ -0,0 +1,199 @@
+use {
+    super::Sensor,
+    std::collections::{HashMap, HashSet},
+};
+
+/// An index of which sensors could overlap with each other, built from the "fingerprint"
+/// of each sensor which is the distances between every pair of points it can see. Two
+/// sensors sharing N points must share at least (N choose 2) of these distances, so we
+/// can find all candidate pairs in one pass over the distances rather than comparing each
+/// sensor with each other one.
+#[derive(Debug)]
+pub struct FingerprintIndex {
+    overlapping: HashSet<(usize, usize)>,
+}
+
+impl FingerprintIndex {
+    /// Builds the index for the given sensors, recording each pair which shares at least the
+    /// given number of distances. A distance appearing several times in each sensor only counts
+    /// as many times as it appears in both.
+    pub fn new(sensors: &[Sensor], minimum_distance_overlap: usize) -> Self {
+        let mut sensors_by_distance = HashMap::<i64, Vec<(usize, usize)>>::new();
+        sensors.iter().for_each(|sensor| {
+            distance_runs(&sensor.distances).for_each(|(distance, run)| {
+                sensors_by_distance
+                    .entry(distance)
+                    .or_default()
+                    .push((sensor.id, run.len()));
+            });
+        });
+        let mut shared = HashMap::<(usize, usize), usize>::new();
+        sensors_by_distance.values().for_each(|entries| {
+            entries
+                .iter()
+                .enumerate()
+                .for_each(|(idx, (id_1, count_1))| {
+                    entries.iter().skip(idx + 1).for_each(|(id_2, count_2)| {
+                        *shared.entry((*id_1, *id_2)).or_default() += count_1.min(count_2);
+                    });
+                });
+        });
+        let overlapping = shared
+            .into_iter()
+            .filter(|(_, count)| *count >= minimum_distance_overlap)
+            .flat_map(|((id_1, id_2), _)| [(id_1, id_2), (id_2, id_1)])
+            .collect();
+        Self { overlapping }
+    }
+
+    /// Checks whether the two sensors share enough distances to possibly overlap.
+    pub fn could_overlap(&self, source: &Sensor, destination: &Sensor) -> bool {
+        self.overlapping.contains(&(source.id, destination.id))
+    }
+}
+
+/// Finds the pairs of (source, destination) point indices which could be the same point. Each
+/// distance shared between the sensors is a vote for either end of the source pair being either
+/// end of the destination pair, and a point in an overlap of N points will have at least N - 1
+/// votes for its partner. Pairs are returned in the same order as a nested loop over source and
+/// then destination points would produce them.
+pub fn candidate_correspondences(
+    source: &Sensor,
+    destination: &Sensor,
+    minimum_overlap: usize,
+) -> Vec<(usize, usize)> {
+    let columns = destination.positions.len();
+    let mut votes = vec![0; source.positions.len() * columns];
+    let mut destination_runs = distance_runs(&destination.distances).peekable();
+    distance_runs(&source.distances).for_each(|(distance, source_run)| {
+        while destination_runs
+            .next_if(|(other, _)| *other < distance)
+            .is_some()
+        {}
+        if let Some((_, destination_run)) =
+            destination_runs.next_if(|(other, _)| *other == distance)
+        {
+            source_run.iter().for_each(|(_, i, j)| {
+                destination_run.iter().for_each(|(_, k, l)| {
+                    [(i, k), (i, l), (j, k), (j, l)].into_iter().for_each(
+                        |(source_idx, destination_idx)| {
+                            votes[source_idx * columns + destination_idx] += 1;
+                        },
+                    );
+                });
+            });
+        }
+    });
+    let required = minimum_overlap.saturating_sub(1);
+    votes
+        .into_iter()
+        .enumerate()
+        .filter(|(_, count)| *count >= required)
+        .map(|(idx, _)| (idx / columns, idx % columns))
+        .collect()
+}
+
+/// Groups a list of distances (sorted by distance) into runs of the same distance.
+fn distance_runs(
+    distances: &[(i64, usize, usize)],
+) -> impl Iterator<Item = (i64, &[(i64, usize, usize)])> {
+    let mut remaining = distances;
+    std::iter::from_fn(move || {
+        let (distance, ..) = *remaining.first()?;
+        let length = remaining
+            .iter()
+            .take_while(|(other, ..)| *other == distance)
+            .count();
+        let (run, rest) = remaining.split_at(length);
+        remaining = rest;
+        Some((distance, run))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{super::DistanceMetric, *},
+        crate::common::math::Vector,
+    };
+
+    #[test]
+    fn test_distance_runs() {
+        let distances = [(1, 0, 1), (1, 0, 2), (3, 1, 2), (4, 0, 3), (4, 1, 3)];
+        assert_eq!(
+            distance_runs(&distances).collect::<Vec<_>>(),
+            vec![
+                (1, &distances[0..2]),
+                (3, &distances[2..3]),
+                (4, &distances[3..5])
+            ]
+        );
+        assert_eq!(distance_runs(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_fingerprint_index() {
+        let metric = DistanceMetric::default();
+        let sensor_0 = Sensor::new(
+            0,
+            vec![
+                Vector::new([0, 0, 0]),
+                Vector::new([1, 0, 0]),
+                Vector::new([0, 3, 0]),
+            ],
+            &metric,
+        );
+        let sensor_1 = Sensor::new(
+            1,
+            vec![
+                Vector::new([5, 5, 5]),
+                Vector::new([5, 6, 5]),
+                Vector::new([5, 5, 8]),
+            ],
+            &metric,
+        );
+        let sensor_2 = Sensor::new(
+            2,
+            vec![
+                Vector::new([0, 0, 0]),
+                Vector::new([2, 0, 0]),
+                Vector::new([0, 5, 0]),
+            ],
+            &metric,
+        );
+        let index =
+            FingerprintIndex::new(&[sensor_0.clone(), sensor_1.clone(), sensor_2.clone()], 3);
+        assert!(index.could_overlap(&sensor_0, &sensor_1));
+        assert!(index.could_overlap(&sensor_1, &sensor_0));
+        assert!(!index.could_overlap(&sensor_0, &sensor_2));
+        assert!(!index.could_overlap(&sensor_1, &sensor_2));
+    }
+
+    #[test]
+    fn test_candidate_correspondences() {
+        let metric = DistanceMetric::default();
+        let source = Sensor::new(
+            0,
+            vec![
+                Vector::new([0, 0, 0]),
+                Vector::new([1, 0, 0]),
+                Vector::new([0, 3, 0]),
+                Vector::new([9, 9, 9]),
+            ],
+            &metric,
+        );
+        let destination = Sensor::new(
+            1,
+            vec![
+                Vector::new([5, 5, 8]),
+                Vector::new([5, 5, 5]),
+                Vector::new([5, 6, 5]),
+            ],
+            &metric,
+        );
+        assert_eq!(
+            candidate_correspondences(&source, &destination, 3),
+            vec![(0, 1), (1, 2), (2, 0)]
+        );
+    }
+}
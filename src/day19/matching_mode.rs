@@ -0,0 +1,12 @@
+/// How points from two sensors are decided to be the same beacon once aligned.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MatchingMode {
+    /// Points must land on exactly the same coordinates, which is what the puzzle data gives.
+    #[default]
+    Exact,
+    /// Points match if every coordinate is within epsilon of each other, for sensor data with
+    /// jitter and stray beacons. Links are scored by how many points match (the inliers) and
+    /// the best link for each sensor is taken, rejecting any links that disagree with the
+    /// consensus of the others. The alignment strategy is not used in this mode.
+    Tolerant { epsilon: i64 },
+}
@@ -1,148 +1,160 @@
-mod rotations;
+mod alignment_strategy;
+mod distance_metric;
+mod fingerprint_index;
+mod matching_mode;
+mod rotation_group;
+mod scanner_map;
+mod scanner_pose;
+mod scanner_solver_config;
 mod sensor;
 mod sensor_link;
-mod sensor_object;
+mod tolerant_alignment;
 
-use crate::common::{iter::FilterGroupMapExt, math::Matrix};
-use sensor::Sensor;
+use crate::common::{
+    iter::FilterGroupMapExt,
+    math::{Matrix, Vector},
+};
+use fingerprint_index::{candidate_correspondences, FingerprintIndex};
+use sensor::{parse_id, parse_positions, Sensor};
 use sensor_link::SensorLink;
-use sensor_object::SensorObject;
 use std::collections::{HashMap, HashSet};
-use std::iter::once;
+use tolerant_alignment::{cluster_points, get_tolerant_sensor_links};
 
-pub fn run(input: &str) -> (usize, i64) {
-    let sensors = parse_into_sensors(input);
-    let processed = vec![&sensors[0]];
-    let unprocessed = sensors.iter().skip(1).collect();
-    let links = get_sensor_links(processed, unprocessed);
-
-    // Start off with a HashMap keyed by sensor and their unrotated
-    // positions for each sensor. Also add in the sensor position itself
-    // which is at (0, 0, 0).
-    let mut hm = sensors
-        .iter()
-        .map(|sensor| {
-            (
-                sensor,
-                sensor.points[0]
-                    .1
-                    .iter()
-                    .map(|position| SensorObject::Beacon(*position))
-                    .chain(once(SensorObject::Sensor((0, 0, 0))))
-                    .collect::<HashSet<_>>(),
-            )
-        })
-        .collect::<HashMap<_, _>>();
-
-    // Go through the links in REVERSE order and take the positions from the
-    // source sensor, apply the given rotation and translation to place them in
-    // the space of the destination sensor. Add these to the destination sensors entry.
-    // We go back in reverse order because we will end up eventually having everything in
-    // the space of Sensor 0.
-    links.into_iter().rev().for_each(|link| {
-        let transformed = hm[link.source]
-            .iter()
-            .map(|obj| transform_sensor_object(obj, link.rotation, &link.translation))
-            .collect::<Vec<_>>();
-        hm.get_mut(link.destination)
-            .unwrap()
-            .extend(transformed.into_iter());
-    });
+pub use alignment_strategy::AlignmentStrategy;
+pub use distance_metric::DistanceMetric;
+pub use matching_mode::MatchingMode;
+pub use rotation_group::RotationGroup;
+pub use scanner_map::ScannerMap;
+pub use scanner_pose::ScannerPose;
+pub use scanner_solver_config::ScannerSolverConfig;
 
-    // Entire map should have consolidated into sensor 0's list.
-    let map = &hm[&sensors[0]];
-
-    // Part 1 is the number of unique beacons. Since we're using HashSet though these beacons will be unique.
-    let part_1 = map
-        .into_iter()
-        .filter(|obj| matches!(obj, SensorObject::Beacon(_)))
-        .count();
-
-    // Part 2 will need to test each sensor against each other to find the largest manhattan distance.
-    let part_2 = map
-        .into_iter()
-        .filter(|obj| matches!(obj, SensorObject::Sensor(..)))
-        .enumerate()
-        .flat_map(|(idx, obj_1)| {
-            map.into_iter()
-                .filter(|obj| matches!(obj, SensorObject::Sensor(..)))
-                .enumerate()
-                .skip(idx + 1)
-                .map(move |(_, obj_2)| match (obj_1, obj_2) {
-                    (SensorObject::Sensor(pos_1), SensorObject::Sensor(pos_2)) => {
-                        manhattan_distance(pos_1, pos_2)
-                    }
-                    _ => unreachable!(),
-                })
-        })
-        .max()
-        .unwrap();
-
-    (part_1, part_2)
+pub fn run(input: &str) -> (usize, i64) {
+    solve_with_config(input, &ScannerSolverConfig::default())
 }
 
-/// Calculates the manhattan distance between two points.
-fn manhattan_distance(p1: &(i64, i64, i64), p2: &(i64, i64, i64)) -> i64 {
-    (p1.0 - p2.0).abs() + (p1.1 - p2.1).abs() + (p1.2 - p2.2).abs()
+/// Aligns all of the sensors in the input with the first one using the given settings, and
+/// returns the number of unique beacons along with the largest manhattan distance between
+/// any two sensors.
+pub fn solve_with_config(input: &str, config: &ScannerSolverConfig) -> (usize, i64) {
+    let map = reconstruct_map(input, config);
+    (map.beacons.len(), map.largest_sensor_distance())
 }
 
-/// Takes a SensorObject, and a reference to a rotation and translation that can be applied
-/// to take it to another sensor's space, and produces a SensorObject that's transformed.
-fn transform_sensor_object(
-    obj: &SensorObject,
-    rotation: &Matrix<i64, 3, 3>,
-    translation: &(i64, i64, i64),
-) -> SensorObject {
-    let pos = match obj {
-        SensorObject::Sensor(pos) | SensorObject::Beacon(pos) => pos,
+/// Aligns all of the sensors in the input with the first one using the given settings, and
+/// builds the map of where every sensor and beacon is in the space of sensor 0. Sensors
+/// which can't be linked to the others are left out of the map.
+pub fn reconstruct_map(input: &str, config: &ScannerSolverConfig) -> ScannerMap {
+    let sensors = parse_into_sensors(input, &config.distance_metric);
+    let rotations = config.rotation_group.matrices();
+    let (links, rejected_links) = match config.matching_mode {
+        MatchingMode::Exact => {
+            let index = match config.alignment_strategy {
+                AlignmentStrategy::BruteForce => None,
+                AlignmentStrategy::Fingerprint => Some(FingerprintIndex::new(
+                    &sensors,
+                    config.minimum_distance_overlap(),
+                )),
+            };
+            let processed = vec![&sensors[0]];
+            let unprocessed = sensors.iter().skip(1).collect();
+            let links =
+                get_sensor_links(processed, unprocessed, &rotations, config, index.as_ref());
+            (links, vec![])
+        }
+        MatchingMode::Tolerant { epsilon } => {
+            get_tolerant_sensor_links(&sensors, &rotations, config, epsilon)
+        }
+    };
+
+    // Each link's destination is always processed before its source, so going through the
+    // links in order we already know the pose of the destination and can chain the link's
+    // rotation and translation onto it to get the pose of the source.
+    let mut poses = HashMap::new();
+    poses.insert(sensors[0].id, ScannerPose::origin(sensors[0].id));
+    links.iter().for_each(|link| {
+        let pose =
+            poses[&link.destination.id].chain(link.source.id, link.rotation, &link.translation);
+        poses.insert(link.source.id, pose);
+    });
+
+    // Place every beacon into the space of sensor 0 (leaving out any sensors which couldn't be
+    // linked), using a HashSet to deduplicate them or clustering them if matching with tolerance.
+    let beacons = sensors.iter().flat_map(|sensor| {
+        poses.get(&sensor.id).into_iter().flat_map(|pose| {
+            sensor
+                .positions
+                .iter()
+                .map(move |position| pose.transform(position))
+        })
+    });
+    let beacons = match config.matching_mode {
+        MatchingMode::Exact => {
+            let mut beacons = beacons
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+            beacons.sort_unstable();
+            beacons
+        }
+        MatchingMode::Tolerant { epsilon } => cluster_points(beacons, epsilon),
     };
-    let pos = rotation * pos;
-    let pos = (
-        pos.0 + translation.0,
-        pos.1 + translation.1,
-        pos.2 + translation.2,
-    );
-    match obj {
-        SensorObject::Sensor(_) => SensorObject::Sensor(pos),
-        SensorObject::Beacon(_) => SensorObject::Beacon(pos),
+    let mut poses = poses.into_values().collect::<Vec<_>>();
+    poses.sort_unstable_by_key(|pose| pose.id);
+
+    ScannerMap {
+        poses,
+        beacons,
+        links: links
+            .iter()
+            .map(|link| (link.source.id, link.destination.id))
+            .collect(),
+        rejected_links,
     }
 }
 
 /// Takes the given &str representing the whole input and parses it into
-/// the list of sensors, containing the visible points in all the bases.
-fn parse_into_sensors(input: &str) -> Vec<Sensor> {
+/// the list of sensors, with the distances between their points measured by the given metric.
+fn parse_into_sensors(input: &str, metric: &DistanceMetric) -> Vec<Sensor> {
     input
         .trim()
         .lines()
         .filter_group_map(
             |elem| !elem.trim().is_empty(),
             |lines| {
-                lines
+                let string = lines
                     .into_iter()
                     .map(|elem| *elem)
                     .collect::<Vec<_>>()
-                    .join("\n")
-                    .parse::<Sensor>()
-                    .unwrap()
+                    .join("\n");
+                Sensor::new(parse_id(&string), parse_positions(&string), metric)
             },
         )
         .collect::<Vec<_>>()
 }
 
 /// Gets links between sensors, enough to be able to trace each one back to
-/// sensor #0.
+/// sensor #0. If a fingerprint index is given it's used to find the candidate
+/// sensor pairs and point correspondences, otherwise every pairing is tried.
+/// Any sensors which can't be linked at all are left out.
 fn get_sensor_links<'a>(
-    mut processed: Vec<&'a Sensor<'a>>,
-    mut unprocessed: Vec<&'a Sensor<'a>>,
+    mut processed: Vec<&'a Sensor>,
+    mut unprocessed: Vec<&'a Sensor>,
+    rotations: &'a [Matrix<i64, 3, 3>],
+    config: &ScannerSolverConfig,
+    index: Option<&FingerprintIndex>,
 ) -> Vec<SensorLink<'a>> {
     let mut links = Vec::with_capacity(unprocessed.len());
-    while !unprocessed.is_empty() {
-        let (link, idx) = unprocessed
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, sensor)| Some((get_single_sensor_link(&processed, sensor)?, idx)))
-            .next()
-            .unwrap();
+    while let Some((link, idx)) = unprocessed
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, sensor)| {
+            Some((
+                get_single_sensor_link(&processed, sensor, rotations, config, index)?,
+                idx,
+            ))
+        })
+        .next()
+    {
         links.push(link);
         let removed = unprocessed.remove(idx);
         processed.push(removed);
@@ -154,12 +166,31 @@ fn get_sensor_links<'a>(
 fn get_single_sensor_link<'a>(
     processed: &[&'a Sensor],
     source: &'a Sensor,
+    rotations: &'a [Matrix<i64, 3, 3>],
+    config: &ScannerSolverConfig,
+    index: Option<&FingerprintIndex>,
 ) -> Option<SensorLink<'a>> {
     processed
         .into_iter()
         .filter_map(|destination| {
-            if do_distances_overlap_enough(source, destination) {
-                find_correct_rotation_and_translation(source, destination)
+            let could_overlap = match index {
+                Some(index) => index.could_overlap(source, destination),
+                None => do_distances_overlap_enough(source, destination, config),
+            };
+            if could_overlap {
+                let candidates = match index {
+                    Some(_) => {
+                        candidate_correspondences(source, destination, config.minimum_overlap)
+                    }
+                    None => all_correspondences(source, destination),
+                };
+                find_correct_rotation_and_translation(
+                    source,
+                    destination,
+                    rotations,
+                    &candidates,
+                    config.minimum_overlap,
+                )
             } else {
                 None
             }
@@ -169,15 +200,22 @@ fn get_single_sensor_link<'a>(
 
 /// Finds the correct rotation and translation if there exists one to map between source and destination
 /// Sensors. Requires an additional translation after rotation to try to align the points such that there
-/// are a minimum of 12 shared after translation.
+/// are a minimum number shared after translation. Only the given (source, destination) pairs of point
+/// indices are tried as the basis of the translation.
 fn find_correct_rotation_and_translation<'a>(
     source: &'a Sensor,
     destination: &'a Sensor,
+    rotations: &'a [Matrix<i64, 3, 3>],
+    candidates: &[(usize, usize)],
+    minimum_overlap: usize,
 ) -> Option<SensorLink<'a>> {
-    (&source.points)
-        .into_iter()
-        .filter_map(|(rotation, points)| {
-            if let Some(translation) = calculate_translation(&points, &destination.points[0].1) {
+    rotations
+        .iter()
+        .filter_map(|rotation| {
+            let points = source.rotated_positions(rotation);
+            if let Some(translation) =
+                calculate_translation(&points, &destination.positions, candidates, minimum_overlap)
+            {
                 Some(SensorLink {
                     source,
                     destination,
@@ -191,69 +229,79 @@ fn find_correct_rotation_and_translation<'a>(
         .next()
 }
 
-/// Takes two sets of points and sees if there's a translation which would take at least 12 points from
-/// source to destination.
+/// Takes two sets of points and sees if there's a translation which would take at least the minimum
+/// overlap of points from source to destination. Translations are tried which would take the source
+/// point onto the destination point of each candidate pair of indices.
 fn calculate_translation(
-    source: &[(i64, i64, i64)],
-    destination: &[(i64, i64, i64)],
-) -> Option<(i64, i64, i64)> {
-    source
-        .into_iter()
-        .flat_map(|source_position| {
-            destination.into_iter().filter_map(|destination_position| {
-                let translation = (
-                    destination_position.0 - source_position.0,
-                    destination_position.1 - source_position.1,
-                    destination_position.2 - source_position.2,
-                );
-                if test_apply_translation(source, destination, &translation) {
-                    Some(translation)
-                } else {
-                    None
-                }
-            })
+    source: &[Vector<i64, 3>],
+    destination: &[Vector<i64, 3>],
+    candidates: &[(usize, usize)],
+    minimum_overlap: usize,
+) -> Option<Vector<i64, 3>> {
+    candidates
+        .iter()
+        .filter_map(|(source_idx, destination_idx)| {
+            let translation = destination[*destination_idx] - source[*source_idx];
+            if test_apply_translation(source, destination, &translation, minimum_overlap) {
+                Some(translation)
+            } else {
+                None
+            }
         })
         .next()
 }
 
+/// Pairs up every point of the source sensor with every point of the destination sensor.
+fn all_correspondences(source: &Sensor, destination: &Sensor) -> Vec<(usize, usize)> {
+    (0..source.positions.len())
+        .flat_map(|source_idx| {
+            (0..destination.positions.len())
+                .map(move |destination_idx| (source_idx, destination_idx))
+        })
+        .collect()
+}
+
 /// Applies the given translation to points in source and tests if enough are present in
-/// destination (12).
+/// destination (the minimum overlap).
 fn test_apply_translation(
-    source: &[(i64, i64, i64)],
-    destination: &[(i64, i64, i64)],
-    translation: &(i64, i64, i64),
+    source: &[Vector<i64, 3>],
+    destination: &[Vector<i64, 3>],
+    translation: &Vector<i64, 3>,
+    minimum_overlap: usize,
 ) -> bool {
     source
         .into_iter()
         .filter(|position| {
-            let position = (
-                position.0 + translation.0,
-                position.1 + translation.1,
-                position.2 + translation.2,
-            );
+            let position = **position + *translation;
             destination.into_iter().any(|point| position == *point)
         })
-        .take(12)
+        .take(minimum_overlap)
         .count()
-        == 12
+        == minimum_overlap
 }
 
 /// Checks whether the two Sensor references actually potentially overlap based on their distances
 /// between any two vertices. Since distances remain the same with rotation, and since we're looking for
-/// 12 intersecting points, the number of distances in common will be at least 66 (12 choose 2).
-fn do_distances_overlap_enough(source: &Sensor, destination: &Sensor) -> bool {
+/// the minimum overlap of intersecting points, the number of distances in common will be at least
+/// (overlap choose 2), e.g. 66 for 12 points.
+fn do_distances_overlap_enough(
+    source: &Sensor,
+    destination: &Sensor,
+    config: &ScannerSolverConfig,
+) -> bool {
+    let required = config.minimum_distance_overlap();
     source
         .distances
         .iter()
-        .filter(|(_, _, source_dist)| {
+        .filter(|(source_dist, ..)| {
             destination
                 .distances
                 .iter()
-                .any(|(_, _, destination_dist)| source_dist == destination_dist)
+                .any(|(destination_dist, ..)| source_dist == destination_dist)
         })
-        .take(66)
+        .take(required)
         .count()
-        == 66
+        == required
 }
 
 #[cfg(test)]
@@ -354,152 +402,358 @@ mod tests {
         .parse::<Sensor>()
         .unwrap();
 
-        assert!(do_distances_overlap_enough(&sensor_0, &sensor_1));
-        assert!(!do_distances_overlap_enough(&sensor_0, &sensor_4));
-        assert!(do_distances_overlap_enough(&sensor_1, &sensor_4));
+        let config = ScannerSolverConfig::default();
+        assert!(do_distances_overlap_enough(&sensor_0, &sensor_1, &config));
+        assert!(!do_distances_overlap_enough(&sensor_0, &sensor_4, &config));
+        assert!(do_distances_overlap_enough(&sensor_1, &sensor_4, &config));
     }
 
+    const EXAMPLE: &str = r#"
+    --- scanner 0 ---
+    404,-588,-901
+    528,-643,409
+    -838,591,734
+    390,-675,-793
+    -537,-823,-458
+    -485,-357,347
+    -345,-311,381
+    -661,-816,-575
+    -876,649,763
+    -618,-824,-621
+    553,345,-567
+    474,580,667
+    -447,-329,318
+    -584,868,-557
+    544,-627,-890
+    564,392,-477
+    455,729,728
+    -892,524,684
+    -689,845,-530
+    423,-701,434
+    7,-33,-71
+    630,319,-379
+    443,580,662
+    -789,900,-551
+    459,-707,401
+
+    --- scanner 1 ---
+    686,422,578
+    605,423,415
+    515,917,-361
+    -336,658,858
+    95,138,22
+    -476,619,847
+    -340,-569,-846
+    567,-361,727
+    -460,603,-452
+    669,-402,600
+    729,430,532
+    -500,-761,534
+    -322,571,750
+    -466,-666,-811
+    -429,-592,574
+    -355,545,-477
+    703,-491,-529
+    -328,-685,520
+    413,935,-424
+    -391,539,-444
+    586,-435,557
+    -364,-763,-893
+    807,-499,-711
+    755,-354,-619
+    553,889,-390
+
+    --- scanner 2 ---
+    649,640,665
+    682,-795,504
+    -784,533,-524
+    -644,584,-595
+    -588,-843,648
+    -30,6,44
+    -674,560,763
+    500,723,-460
+    609,671,-379
+    -555,-800,653
+    -675,-892,-343
+    697,-426,-610
+    578,704,681
+    493,664,-388
+    -671,-858,530
+    -667,343,800
+    571,-461,-707
+    -138,-166,112
+    -889,563,-600
+    646,-828,498
+    640,759,510
+    -630,509,768
+    -681,-892,-333
+    673,-379,-804
+    -742,-814,-386
+    577,-820,562
+
+    --- scanner 3 ---
+    -589,542,597
+    605,-692,669
+    -500,565,-823
+    -660,373,557
+    -458,-679,-417
+    -488,449,543
+    -626,468,-788
+    338,-750,-386
+    528,-832,-391
+    562,-778,733
+    -938,-730,414
+    543,643,-506
+    -524,371,-870
+    407,773,750
+    -104,29,83
+    378,-903,-323
+    -778,-728,485
+    426,699,580
+    -438,-605,-362
+    -469,-447,-387
+    509,732,623
+    647,635,-688
+    -868,-804,481
+    614,-800,639
+    595,780,-596
+
+    --- scanner 4 ---
+    727,592,562
+    -293,-554,779
+    441,611,-461
+    -714,465,-776
+    -743,427,-804
+    -660,-479,-426
+    832,-632,460
+    927,-485,-438
+    408,393,-506
+    466,436,-512
+    110,16,151
+    -258,-428,682
+    -393,719,612
+    -211,-452,876
+    808,-476,-593
+    -575,615,604
+    -485,667,467
+    -680,325,-822
+    -627,-443,-432
+    872,-547,-609
+    833,512,582
+    807,604,487
+    839,-516,451
+    891,-625,532
+    -652,-548,-490
+    30,-46,-14
+    "#;
+
     #[test]
     fn test_run() {
-        const INPUT: &str = r#"
-        --- scanner 0 ---
-        404,-588,-901
-        528,-643,409
-        -838,591,734
-        390,-675,-793
-        -537,-823,-458
-        -485,-357,347
-        -345,-311,381
-        -661,-816,-575
-        -876,649,763
-        -618,-824,-621
-        553,345,-567
-        474,580,667
-        -447,-329,318
-        -584,868,-557
-        544,-627,-890
-        564,392,-477
-        455,729,728
-        -892,524,684
-        -689,845,-530
-        423,-701,434
-        7,-33,-71
-        630,319,-379
-        443,580,662
-        -789,900,-551
-        459,-707,401
+        const EXPECTED: (usize, i64) = (79, 3621);
+        assert_eq!(run(EXAMPLE), EXPECTED);
+    }
 
-        --- scanner 1 ---
-        686,422,578
-        605,423,415
-        515,917,-361
-        -336,658,858
-        95,138,22
-        -476,619,847
-        -340,-569,-846
-        567,-361,727
-        -460,603,-452
-        669,-402,600
-        729,430,532
-        -500,-761,534
-        -322,571,750
-        -466,-666,-811
-        -429,-592,574
-        -355,545,-477
-        703,-491,-529
-        -328,-685,520
-        413,935,-424
-        -391,539,-444
-        586,-435,557
-        -364,-763,-893
-        807,-499,-711
-        755,-354,-619
-        553,889,-390
+    #[test]
+    fn test_reconstruct_map() {
+        let map = reconstruct_map(EXAMPLE, &ScannerSolverConfig::default());
+        assert_eq!(map.beacons.len(), 79);
+        assert!(map.beacons.contains(&Vector::new([-892, 524, 684])));
+        assert!(map.beacons.contains(&Vector::new([-447, -329, 318])));
+        assert_eq!(
+            map.poses
+                .iter()
+                .map(|pose| pose.translation)
+                .collect::<Vec<_>>(),
+            vec![
+                Vector::new([0, 0, 0]),
+                Vector::new([68, -1246, -43]),
+                Vector::new([1105, -1205, 1229]),
+                Vector::new([-92, -2380, -20]),
+                Vector::new([-20, -1133, 1061])
+            ]
+        );
+        assert_eq!(map.links.len(), 4);
+        map.links.iter().for_each(|(source, destination)| {
+            assert!(source != destination);
+        });
+        // beacons seen by scanner 1 should land in the same place in the map
+        assert_eq!(
+            map.poses[1].transform(&Vector::new([686, 422, 578])),
+            Vector::new([-618, -824, -621])
+        );
+    }
 
-        --- scanner 2 ---
-        649,640,665
-        682,-795,504
-        -784,533,-524
-        -644,584,-595
-        -588,-843,648
-        -30,6,44
-        -674,560,763
-        500,723,-460
-        609,671,-379
-        -555,-800,653
-        -675,-892,-343
-        697,-426,-610
-        578,704,681
-        493,664,-388
-        -671,-858,530
-        -667,343,800
-        571,-461,-707
-        -138,-166,112
-        -889,563,-600
-        646,-828,498
-        640,759,510
-        -630,509,768
-        -681,-892,-333
-        673,-379,-804
-        -742,-814,-386
-        577,-820,562
-
-        --- scanner 3 ---
-        -589,542,597
-        605,-692,669
-        -500,565,-823
-        -660,373,557
-        -458,-679,-417
-        -488,449,543
-        -626,468,-788
-        338,-750,-386
-        528,-832,-391
-        562,-778,733
-        -938,-730,414
-        543,643,-506
-        -524,371,-870
-        407,773,750
-        -104,29,83
-        378,-903,-323
-        -778,-728,485
-        426,699,580
-        -438,-605,-362
-        -469,-447,-387
-        509,732,623
-        647,635,-688
-        -868,-804,481
-        614,-800,639
-        595,780,-596
+    #[test]
+    fn test_solve_with_config_tolerant_exact_data() {
+        let config = ScannerSolverConfig {
+            matching_mode: MatchingMode::Tolerant { epsilon: 0 },
+            ..Default::default()
+        };
+        assert_eq!(solve_with_config(EXAMPLE, &config), (79, 3621));
+    }
 
-        --- scanner 4 ---
-        727,592,562
-        -293,-554,779
-        441,611,-461
-        -714,465,-776
-        -743,427,-804
-        -660,-479,-426
-        832,-632,460
-        927,-485,-438
-        408,393,-506
-        466,436,-512
-        110,16,151
-        -258,-428,682
-        -393,719,612
-        -211,-452,876
-        808,-476,-593
-        -575,615,604
-        -485,667,467
-        -680,325,-822
-        -627,-443,-432
-        872,-547,-609
-        833,512,582
-        807,604,487
-        839,-516,451
-        891,-625,532
-        -652,-548,-490
-        30,-46,-14
-        "#;
-        const EXPECTED: (usize, i64) = (79, 3621);
-        assert_eq!(run(INPUT), EXPECTED);
+    #[test]
+    fn test_reconstruct_map_tolerant_noisy_data() {
+        // Jitter every coordinate seen by scanners other than 0 by up to 1 in either
+        // direction, and give each scanner but the last a stray beacon nothing else can see.
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut jitter = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 3) as i64 - 1
+        };
+        let mut scanner = 0;
+        let input = EXAMPLE
+            .trim()
+            .lines()
+            .map(|line| line.trim())
+            .flat_map(|line| {
+                if line.starts_with("---") {
+                    scanner = parse_id(line);
+                    vec![line.to_string()]
+                } else if line.is_empty() {
+                    vec![
+                        format!("{},{},{}", 2000, 2000 + scanner, 2000),
+                        String::new(),
+                    ]
+                } else if scanner == 0 {
+                    vec![line.to_string()]
+                } else {
+                    vec![line
+                        .split(',')
+                        .map(|coord| (coord.parse::<i64>().unwrap() + jitter()).to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")]
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let exact = ScannerSolverConfig::default();
+        let tolerant = ScannerSolverConfig {
+            matching_mode: MatchingMode::Tolerant { epsilon: 2 },
+            ..Default::default()
+        };
+        let map = reconstruct_map(&input, &tolerant);
+        assert_eq!(map.poses.len(), 5);
+        assert!(map.rejected_links.is_empty());
+        assert_eq!(map.beacons.len(), 79 + 4);
+        assert!((map.largest_sensor_distance() - 3621).abs() <= 6);
+        assert_eq!(parse_into_sensors(&input, &exact.distance_metric).len(), 5);
+    }
+
+    #[test]
+    fn test_solve_with_config_unlinked_sensors() {
+        // The example's scanners only share 12 beacons, so none of them can be linked.
+        let config = ScannerSolverConfig {
+            minimum_overlap: 13,
+            ..Default::default()
+        };
+        assert_eq!(solve_with_config(EXAMPLE, &config), (25, 0));
+        let input = format!("{}\n--- scanner 5 ---\n1,2,3\n4,5,6", EXAMPLE);
+        assert_eq!(run(&input), (79, 3621));
+    }
+
+    #[test]
+    fn test_solve_with_config_distance_metrics() {
+        [DistanceMetric::Chebyshev, DistanceMetric::SquaredEuclidean]
+            .into_iter()
+            .for_each(|distance_metric| {
+                let config = ScannerSolverConfig {
+                    distance_metric,
+                    ..Default::default()
+                };
+                assert_eq!(solve_with_config(EXAMPLE, &config), (79, 3621));
+            });
+    }
+
+    #[test]
+    fn test_alignment_strategies_identical_links() {
+        let config = ScannerSolverConfig::default();
+        let sensors = parse_into_sensors(EXAMPLE, &config.distance_metric);
+        let rotations = config.rotation_group.matrices();
+        let index = FingerprintIndex::new(&sensors, config.minimum_distance_overlap());
+        let get_links = |index| {
+            get_sensor_links(
+                vec![&sensors[0]],
+                sensors.iter().skip(1).collect(),
+                &rotations,
+                &config,
+                index,
+            )
+            .into_iter()
+            .map(|link| {
+                (
+                    link.source.id,
+                    link.destination.id,
+                    link.rotation.clone(),
+                    link.translation,
+                )
+            })
+            .collect::<Vec<_>>()
+        };
+        let brute_force = get_links(None);
+        assert_eq!(brute_force.len(), 4);
+        assert_eq!(brute_force, get_links(Some(&index)));
+    }
+
+    #[test]
+    fn test_solve_with_config_alignment_strategies() {
+        let config = ScannerSolverConfig {
+            alignment_strategy: AlignmentStrategy::BruteForce,
+            ..Default::default()
+        };
+        assert_eq!(solve_with_config(EXAMPLE, &config), (79, 3621));
+    }
+
+    #[test]
+    fn test_solve_with_config_reflections() {
+        // Mirror everything scanner 1 sees along the x axis, which can only be aligned
+        // if reflections are allowed.
+        let mut in_scanner_1 = false;
+        let input = EXAMPLE
+            .lines()
+            .map(|line| {
+                let line = line.trim();
+                if line.starts_with("---") {
+                    in_scanner_1 = line == "--- scanner 1 ---";
+                    line.to_string()
+                } else if in_scanner_1 && !line.is_empty() {
+                    format!(
+                        "{}",
+                        -line.split(',').next().unwrap().parse::<i64>().unwrap()
+                    ) + &line[line.find(',').unwrap()..]
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let sensors = parse_into_sensors(&input, &DistanceMetric::default());
+        let proper = RotationGroup::Proper.matrices();
+        let reflections = RotationGroup::WithReflections.matrices();
+        let config = ScannerSolverConfig::default();
+        let candidates = all_correspondences(&sensors[1], &sensors[0]);
+        assert!(find_correct_rotation_and_translation(
+            &sensors[1],
+            &sensors[0],
+            &proper,
+            &candidates,
+            12
+        )
+        .is_none());
+        assert!(find_correct_rotation_and_translation(
+            &sensors[1],
+            &sensors[0],
+            &reflections,
+            &candidates,
+            12
+        )
+        .is_some());
+        let config = ScannerSolverConfig {
+            rotation_group: RotationGroup::WithReflections,
+            ..config
+        };
+        assert_eq!(solve_with_config(&input, &config), (79, 3621));
     }
 }
@@ -0,0 +1,41 @@
+use crate::common::math::{proper_rotations, signed_permutations, Matrix};
+
+/// The set of orientations that are tried when aligning one sensor onto another.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RotationGroup {
+    /// The 24 rotations of a cube, which is what the puzzle sensors can be facing.
+    #[default]
+    Proper,
+    /// The 24 rotations along with their mirror images, for sensors which may also
+    /// have one of their axes flipped.
+    WithReflections,
+}
+
+impl RotationGroup {
+    /// Builds the matrices of the group. The identity rotation is always placed first.
+    pub fn matrices(&self) -> Vec<Matrix<i64, 3, 3>> {
+        match self {
+            Self::Proper => proper_rotations(),
+            Self::WithReflections => signed_permutations(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_matrices() {
+        let proper = RotationGroup::Proper.matrices();
+        let reflections = RotationGroup::WithReflections.matrices();
+        assert_eq!(proper.iter().collect::<HashSet<_>>().len(), 24);
+        assert_eq!(reflections.iter().collect::<HashSet<_>>().len(), 48);
+        assert_eq!(proper[0], Matrix::identity());
+        assert_eq!(reflections[0], proper[0]);
+        assert!(reflections[24..]
+            .iter()
+            .all(|reflection| reflection.determinant() == -1));
+    }
+}
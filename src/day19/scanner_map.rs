@@ -0,0 +1,203 @@
+use {
+    super::scanner_pose::ScannerPose,
+    crate::common::math::Vector,
+    std::io::{Result, Write},
+};
+
+/// The fully reconstructed map of the sensors and beacons, all in the space of sensor 0.
+/// Poses are ordered by sensor id and beacons are deduplicated and sorted. Also keeps the
+/// (source, destination) sensor ids of the links which were used to build it, and of any
+/// links which were rejected for disagreeing with the others when matching with tolerance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScannerMap {
+    pub poses: Vec<ScannerPose>,
+    pub beacons: Vec<Vector<i64, 3>>,
+    pub links: Vec<(usize, usize)>,
+    pub rejected_links: Vec<(usize, usize)>,
+}
+
+impl ScannerMap {
+    /// Finds the largest manhattan distance between any two sensors.
+    pub fn largest_sensor_distance(&self) -> i64 {
+        self.poses
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, pose_1)| {
+                self.poses
+                    .iter()
+                    .skip(idx + 1)
+                    .map(move |pose_2| pose_1.translation.manhattan_distance(&pose_2.translation))
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Writes the beacon positions as CSV with a header row.
+    pub fn write_beacons_csv(&self, writer: &mut impl Write) -> Result<()> {
+        writeln!(writer, "x,y,z")?;
+        self.beacons
+            .iter()
+            .map(Vector::as_array)
+            .try_for_each(|[x, y, z]| writeln!(writer, "{},{},{}", x, y, z))
+    }
+
+    /// Writes the sensor poses as CSV with a header row. The rotation is written out in
+    /// row major order after the translation.
+    pub fn write_poses_csv(&self, writer: &mut impl Write) -> Result<()> {
+        writeln!(writer, "id,x,y,z,r00,r01,r02,r10,r11,r12,r20,r21,r22")?;
+        self.poses.iter().try_for_each(|pose| {
+            let [x, y, z] = pose.translation.into_array();
+            write!(writer, "{},{},{},{}", pose.id, x, y, z)?;
+            pose.rotation
+                .rows()
+                .iter()
+                .try_for_each(|[r0, r1, r2]| write!(writer, ",{},{},{}", r0, r1, r2))?;
+            writeln!(writer)
+        })
+    }
+
+    /// Writes the sensors and beacons as an ASCII PLY point cloud. Sensors are coloured red and
+    /// beacons white so they can be told apart in a viewer.
+    pub fn write_ply(&self, writer: &mut impl Write) -> Result<()> {
+        writeln!(writer, "ply")?;
+        writeln!(writer, "format ascii 1.0")?;
+        writeln!(
+            writer,
+            "element vertex {}",
+            self.poses.len() + self.beacons.len()
+        )?;
+        ["x", "y", "z"]
+            .into_iter()
+            .try_for_each(|axis| writeln!(writer, "property int {}", axis))?;
+        ["red", "green", "blue"]
+            .into_iter()
+            .try_for_each(|channel| writeln!(writer, "property uchar {}", channel))?;
+        writeln!(writer, "end_header")?;
+        self.poses.iter().try_for_each(|pose| {
+            let [x, y, z] = pose.translation.into_array();
+            writeln!(writer, "{} {} {} 255 0 0", x, y, z)
+        })?;
+        self.beacons
+            .iter()
+            .map(Vector::as_array)
+            .try_for_each(|[x, y, z]| writeln!(writer, "{} {} {} 255 255 255", x, y, z))
+    }
+
+    /// Writes the sensors and beacons as vertices of a Wavefront OBJ file, in separate
+    /// objects named "sensors" and "beacons".
+    pub fn write_obj(&self, writer: &mut impl Write) -> Result<()> {
+        writeln!(writer, "o sensors")?;
+        self.poses.iter().try_for_each(|pose| {
+            let [x, y, z] = pose.translation.into_array();
+            writeln!(writer, "v {} {} {}", x, y, z)
+        })?;
+        writeln!(writer, "o beacons")?;
+        self.beacons
+            .iter()
+            .map(Vector::as_array)
+            .try_for_each(|[x, y, z]| writeln!(writer, "v {} {} {}", x, y, z))
+    }
+
+    /// Writes the links which were used to align the sensors as a Graphviz DOT graph, with
+    /// an edge from each sensor to the one that it was aligned onto. Rejected links are drawn
+    /// as dashed red edges.
+    pub fn write_link_graph(&self, writer: &mut impl Write) -> Result<()> {
+        writeln!(writer, "digraph sensors {{")?;
+        self.poses.iter().try_for_each(|pose| {
+            let [x, y, z] = pose.translation.into_array();
+            writeln!(
+                writer,
+                "    {} [label=\"sensor {}\\n{},{},{}\"];",
+                pose.id, pose.id, x, y, z
+            )
+        })?;
+        self.links.iter().try_for_each(|(source, destination)| {
+            writeln!(writer, "    {} -> {};", source, destination)
+        })?;
+        self.rejected_links
+            .iter()
+            .try_for_each(|(source, destination)| {
+                writeln!(
+                    writer,
+                    "    {} -> {} [style=dashed, color=red];",
+                    source, destination
+                )
+            })?;
+        writeln!(writer, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::common::math::Matrix};
+
+    fn example_map() -> ScannerMap {
+        let origin = ScannerPose::origin(0);
+        let rotate_z = Matrix::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+        let second = origin.chain(1, &rotate_z, &Vector::new([10, -2, 3]));
+        ScannerMap {
+            poses: vec![origin, second],
+            beacons: vec![Vector::new([-1, 2, 3]), Vector::new([4, 5, 6])],
+            links: vec![(1, 0)],
+            rejected_links: vec![(0, 1)],
+        }
+    }
+
+    fn write_to_string(func: impl Fn(&mut Vec<u8>) -> Result<()>) -> String {
+        let mut buffer = vec![];
+        func(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_largest_sensor_distance() {
+        assert_eq!(example_map().largest_sensor_distance(), 15);
+    }
+
+    #[test]
+    fn test_write_beacons_csv() {
+        assert_eq!(
+            write_to_string(|writer| example_map().write_beacons_csv(writer)),
+            "x,y,z\n-1,2,3\n4,5,6\n"
+        );
+    }
+
+    #[test]
+    fn test_write_poses_csv() {
+        assert_eq!(
+            write_to_string(|writer| example_map().write_poses_csv(writer)),
+            "id,x,y,z,r00,r01,r02,r10,r11,r12,r20,r21,r22\n\
+             0,0,0,0,1,0,0,0,1,0,0,0,1\n\
+             1,10,-2,3,0,-1,0,1,0,0,0,0,1\n"
+        );
+    }
+
+    #[test]
+    fn test_write_ply() {
+        assert_eq!(
+            write_to_string(|writer| example_map().write_ply(writer)),
+            "ply\nformat ascii 1.0\nelement vertex 4\n\
+             property int x\nproperty int y\nproperty int z\n\
+             property uchar red\nproperty uchar green\nproperty uchar blue\nend_header\n\
+             0 0 0 255 0 0\n10 -2 3 255 0 0\n-1 2 3 255 255 255\n4 5 6 255 255 255\n"
+        );
+    }
+
+    #[test]
+    fn test_write_obj() {
+        assert_eq!(
+            write_to_string(|writer| example_map().write_obj(writer)),
+            "o sensors\nv 0 0 0\nv 10 -2 3\no beacons\nv -1 2 3\nv 4 5 6\n"
+        );
+    }
+
+    #[test]
+    fn test_write_link_graph() {
+        assert_eq!(
+            write_to_string(|writer| example_map().write_link_graph(writer)),
+            "digraph sensors {\n    0 [label=\"sensor 0\\n0,0,0\"];\n    \
+             1 [label=\"sensor 1\\n10,-2,3\"];\n    1 -> 0;\n    \
+             0 -> 1 [style=dashed, color=red];\n}\n"
+        );
+    }
+}
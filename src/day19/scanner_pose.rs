@@ -0,0 +1,71 @@
+use crate::common::math::{Matrix, Vector};
+
+/// The absolute pose of a sensor, relative to sensor 0. A point seen by the sensor is
+/// placed in the space of sensor 0 by applying the rotation followed by the translation,
+/// which means the translation is also the position of the sensor itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScannerPose {
+    pub id: usize,
+    pub rotation: Matrix<i64, 3, 3>,
+    pub translation: Vector<i64, 3>,
+}
+
+impl ScannerPose {
+    /// The pose of sensor 0, which everything else is placed relative to.
+    pub fn origin(id: usize) -> Self {
+        Self {
+            id,
+            rotation: Matrix::identity(),
+            translation: Vector::new([0, 0, 0]),
+        }
+    }
+
+    /// Produces the pose of a sensor which is linked to this one by the given rotation and
+    /// translation (that take points from the linked sensor into the space of this one).
+    pub fn chain(
+        &self,
+        id: usize,
+        rotation: &Matrix<i64, 3, 3>,
+        translation: &Vector<i64, 3>,
+    ) -> Self {
+        Self {
+            id,
+            rotation: &self.rotation * rotation,
+            translation: self.transform(translation),
+        }
+    }
+
+    /// Places a point seen by this sensor into the space of sensor 0.
+    pub fn transform(&self, point: &Vector<i64, 3>) -> Vector<i64, 3> {
+        &self.rotation * point + self.translation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_transform() {
+        assert_eq!(
+            ScannerPose::origin(0).transform(&Vector::new([1, -2, 3])),
+            Vector::new([1, -2, 3])
+        );
+    }
+
+    #[test]
+    fn test_chain() {
+        let rotate_z = Matrix::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+        let first = ScannerPose::origin(0).chain(1, &rotate_z, &Vector::new([10, 0, 0]));
+        let second = first.chain(2, &rotate_z, &Vector::new([0, 5, 0]));
+        assert_eq!(
+            first.transform(&Vector::new([1, 0, 0])),
+            Vector::new([10, 1, 0])
+        );
+        assert_eq!(second.translation, Vector::new([5, 0, 0]));
+        assert_eq!(
+            second.transform(&Vector::new([1, 0, 0])),
+            Vector::new([4, 0, 0])
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use super::{
+    alignment_strategy::AlignmentStrategy, distance_metric::DistanceMetric,
+    matching_mode::MatchingMode, rotation_group::RotationGroup,
+};
+
+/// Settings for aligning the sensors with each other. The defaults are what the
+/// puzzle guarantees, a minimum of 12 shared beacons between overlapping sensors that
+/// can be facing in any of the 24 rotations, found using the fingerprint index with
+/// points matching exactly. In tolerant matching mode the minimum overlap is the fewest
+/// inliers a link can have.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScannerSolverConfig {
+    pub minimum_overlap: usize,
+    pub rotation_group: RotationGroup,
+    pub distance_metric: DistanceMetric,
+    pub alignment_strategy: AlignmentStrategy,
+    pub matching_mode: MatchingMode,
+}
+
+impl ScannerSolverConfig {
+    /// The number of beacon to beacon distances that two sensors must have in common for
+    /// them to possibly share the minimum overlap of beacons, which is (overlap choose 2).
+    pub fn minimum_distance_overlap(&self) -> usize {
+        self.minimum_overlap * self.minimum_overlap.saturating_sub(1) / 2
+    }
+}
+
+impl Default for ScannerSolverConfig {
+    fn default() -> Self {
+        Self {
+            minimum_overlap: 12,
+            rotation_group: RotationGroup::default(),
+            distance_metric: DistanceMetric::default(),
+            alignment_strategy: AlignmentStrategy::default(),
+            matching_mode: MatchingMode::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimum_distance_overlap() {
+        let mut config = ScannerSolverConfig::default();
+        assert_eq!(config.minimum_distance_overlap(), 66);
+        config.minimum_overlap = 3;
+        assert_eq!(config.minimum_distance_overlap(), 3);
+        config.minimum_overlap = 0;
+        assert_eq!(config.minimum_distance_overlap(), 0);
+    }
+}
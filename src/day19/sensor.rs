@@ -1,84 +1,108 @@
 use {
-    super::{manhattan_distance, rotations::ROTATIONS},
-    crate::common::math::Matrix,
+    super::distance_metric::DistanceMetric,
+    crate::common::math::{Matrix, Vector},
     std::{convert::Infallible, str::FromStr},
 };
 
 /// Represents a single Sensor in the puzzle which can detect a certain number of points
-/// from whatever its rotation happens to be. The points of a sensor will be stored for each
-/// rotation.
+/// from whatever its rotation happens to be. The points are stored as the sensor sees them
+/// and are only rotated when trying to align against another sensor. The distances between
+/// each pair of points are calculated up front though as they're checked much more often, and
+/// are stored sorted by distance along with the indices of the two points.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
-pub struct Sensor<'a> {
-    pub points: [(&'a Matrix<i64, 3, 3>, Vec<(i64, i64, i64)>); 24],
-    pub distances: Vec<((i64, i64, i64), (i64, i64, i64), i64)>,
+pub struct Sensor {
+    pub id: usize,
+    pub positions: Vec<Vector<i64, 3>>,
+    pub distances: Vec<(i64, usize, usize)>,
 }
 
-/// Applies the rotation at the given index to the list of points
-/// given and returns the pairing of reference to the rotation, and the
-/// new vector.
-fn transform_positions(
-    points: &[(i64, i64, i64)],
-    idx: usize,
-) -> (&'static Matrix<i64, 3, 3>, Vec<(i64, i64, i64)>) {
-    (
-        &ROTATIONS[idx],
-        points.iter().map(|point| &ROTATIONS[idx] * point).collect(),
-    )
-}
-
-impl FromStr for Sensor<'_> {
-    type Err = Infallible; // just fail on error.
-
-    fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let positions = string
-            .trim()
-            .lines()
-            .skip(1)
-            .map(|line| {
-                let mut splits = line.trim().split(",");
-                (
-                    splits.next().unwrap().parse::<i64>().unwrap(),
-                    splits.next().unwrap().parse::<i64>().unwrap(),
-                    splits.next().unwrap().parse::<i64>().unwrap(),
-                )
-            })
-            .collect::<Vec<_>>();
-        let points = [
-            transform_positions(&positions, 0),
-            transform_positions(&positions, 1),
-            transform_positions(&positions, 2),
-            transform_positions(&positions, 3),
-            transform_positions(&positions, 4),
-            transform_positions(&positions, 5),
-            transform_positions(&positions, 6),
-            transform_positions(&positions, 7),
-            transform_positions(&positions, 8),
-            transform_positions(&positions, 9),
-            transform_positions(&positions, 10),
-            transform_positions(&positions, 11),
-            transform_positions(&positions, 12),
-            transform_positions(&positions, 13),
-            transform_positions(&positions, 14),
-            transform_positions(&positions, 15),
-            transform_positions(&positions, 16),
-            transform_positions(&positions, 17),
-            transform_positions(&positions, 18),
-            transform_positions(&positions, 19),
-            transform_positions(&positions, 20),
-            transform_positions(&positions, 21),
-            transform_positions(&positions, 22),
-            transform_positions(&positions, 23),
-        ];
-        let distances = positions
+impl Sensor {
+    /// Constructs a new sensor from the points it can see, calculating the distances
+    /// between them with the given metric.
+    pub fn new(id: usize, positions: Vec<Vector<i64, 3>>, metric: &DistanceMetric) -> Self {
+        let mut distances = positions
             .iter()
             .enumerate()
-            .flat_map(|(idx, p1)| {
+            .flat_map(|(idx_1, p1)| {
                 positions
                     .iter()
-                    .skip(idx + 1)
-                    .map(move |p2| (*p1, *p2, manhattan_distance(p1, p2)))
+                    .enumerate()
+                    .skip(idx_1 + 1)
+                    .map(move |(idx_2, p2)| (metric.distance(p1, p2), idx_1, idx_2))
             })
-            .collect();
-        Ok(Self { points, distances })
+            .collect::<Vec<_>>();
+        distances.sort_unstable();
+        Self {
+            id,
+            positions,
+            distances,
+        }
+    }
+
+    /// Applies the given rotation to the points of the sensor.
+    pub fn rotated_positions(&self, rotation: &Matrix<i64, 3, 3>) -> Vec<Vector<i64, 3>> {
+        self.positions
+            .iter()
+            .map(|position| rotation * position)
+            .collect()
+    }
+}
+
+/// Parses the number of the sensor from its heading line, e.g. "--- scanner 3 ---".
+pub fn parse_id(string: &str) -> usize {
+    string
+        .trim()
+        .lines()
+        .next()
+        .unwrap()
+        .trim()
+        .trim_start_matches("--- scanner")
+        .trim_end_matches("---")
+        .trim()
+        .parse()
+        .unwrap()
+}
+
+/// Parses the points listed under the sensor's heading line.
+pub fn parse_positions(string: &str) -> Vec<Vector<i64, 3>> {
+    string
+        .trim()
+        .lines()
+        .skip(1)
+        .map(|line| line.parse().unwrap())
+        .collect()
+}
+
+impl FromStr for Sensor {
+    type Err = Infallible; // just fail on error.
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(
+            parse_id(string),
+            parse_positions(string),
+            &DistanceMetric::default(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let sensor = "--- scanner 7 ---\n1,2,3\n-1,0,4\n0,0,0"
+            .parse::<Sensor>()
+            .unwrap();
+        assert_eq!(sensor.id, 7);
+        assert_eq!(
+            sensor.positions,
+            vec![
+                Vector::new([1, 2, 3]),
+                Vector::new([-1, 0, 4]),
+                Vector::new([0, 0, 0])
+            ]
+        );
+        assert_eq!(sensor.distances, vec![(5, 0, 1), (5, 1, 2), (6, 0, 2)]);
     }
 }
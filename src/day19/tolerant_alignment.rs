@@ -0,0 +1,418 @@
+use {
+    super::{
+        distance_metric::DistanceMetric, scanner_pose::ScannerPose, ScannerSolverConfig, Sensor,
+        SensorLink,
+    },
+    crate::common::math::{Matrix, Vector},
+    std::{cmp::Reverse, collections::HashMap},
+};
+
+/// A candidate link between two sensors along with the number of points which match
+/// once the source sensor's points are moved into the space of the destination.
+#[derive(Clone, Debug)]
+pub struct ScoredLink<'a> {
+    pub link: SensorLink<'a>,
+    pub inliers: usize,
+}
+
+/// Gets links between sensors while allowing points to match within epsilon of each other.
+/// Ties between scores are always broken by taking the first, so that the result is
+/// deterministic. Each round the best scoring link from any unprocessed sensor onto a
+/// processed one is taken, after first checking it against the other links from the same
+/// sensor. Returns the links that were used along with the (source, destination) ids of the
+/// links which were rejected by that consensus step. Any sensors which can't be linked at all
+/// are left out.
+pub fn get_tolerant_sensor_links<'a>(
+    sensors: &'a [Sensor],
+    rotations: &'a [Matrix<i64, 3, 3>],
+    config: &ScannerSolverConfig,
+    epsilon: i64,
+) -> (Vec<SensorLink<'a>>, Vec<(usize, usize)>) {
+    let mut poses = HashMap::new();
+    poses.insert(sensors[0].id, ScannerPose::origin(sensors[0].id));
+    let mut processed = vec![&sensors[0]];
+    let mut unprocessed = sensors.iter().skip(1).collect::<Vec<_>>();
+    let mut scores = HashMap::new();
+    let mut links = Vec::with_capacity(unprocessed.len());
+    let mut rejected = vec![];
+    loop {
+        let best = unprocessed
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, source)| {
+                let candidates = processed
+                    .iter()
+                    .filter_map(|destination| {
+                        scores
+                            .entry((source.id, destination.id))
+                            .or_insert_with(|| {
+                                score_link(source, destination, rotations, config, epsilon)
+                            })
+                            .clone()
+                    })
+                    .collect::<Vec<_>>();
+                Some((idx, choose_by_consensus(candidates, &poses, epsilon)?))
+            })
+            .min_by_key(|(_, (chosen, _))| Reverse(chosen.inliers));
+        if let Some((idx, (chosen, disagreeing))) = best {
+            let link = chosen.link;
+            let pose =
+                poses[&link.destination.id].chain(link.source.id, link.rotation, &link.translation);
+            poses.insert(link.source.id, pose);
+            rejected.extend(
+                disagreeing
+                    .iter()
+                    .map(|scored| (scored.link.source.id, scored.link.destination.id)),
+            );
+            links.push(link);
+            processed.push(unprocessed.remove(idx));
+        } else {
+            break;
+        }
+    }
+    (links, rejected)
+}
+
+/// Scores the best way of linking the source sensor onto the destination. Candidate point
+/// correspondences are found from the distances between points (within the tolerance of the
+/// metric), and each one is tried as the basis of a translation for each rotation, keeping
+/// whichever gives the most inliers. The translation is then refined by taking the median of
+/// the offsets between all the inliers. Links with fewer inliers than the minimum overlap
+/// aren't returned at all.
+pub fn score_link<'a>(
+    source: &'a Sensor,
+    destination: &'a Sensor,
+    rotations: &'a [Matrix<i64, 3, 3>],
+    config: &ScannerSolverConfig,
+    epsilon: i64,
+) -> Option<ScoredLink<'a>> {
+    let candidates = tolerant_correspondences(
+        source,
+        destination,
+        &config.distance_metric,
+        epsilon,
+        config.minimum_overlap,
+    );
+    let (rotation, points, translation, inliers) = rotations
+        .iter()
+        .filter_map(|rotation| {
+            let points = source.rotated_positions(rotation);
+            let (translation, inliers) = candidates
+                .iter()
+                .map(|(source_idx, destination_idx)| {
+                    let translation = destination.positions[*destination_idx] - points[*source_idx];
+                    let inliers =
+                        count_inliers(&points, &destination.positions, &translation, epsilon);
+                    (translation, inliers)
+                })
+                .min_by_key(|(_, inliers)| Reverse(*inliers))?;
+            Some((rotation, points, translation, inliers))
+        })
+        .min_by_key(|(.., inliers)| Reverse(*inliers))?;
+    let refined = refine_translation(&points, &destination.positions, &translation, epsilon);
+    let refined_inliers = count_inliers(&points, &destination.positions, &refined, epsilon);
+    let (translation, inliers) = if refined_inliers >= inliers {
+        (refined, refined_inliers)
+    } else {
+        (translation, inliers)
+    };
+    if inliers >= config.minimum_overlap {
+        Some(ScoredLink {
+            link: SensorLink {
+                source,
+                destination,
+                rotation,
+                translation,
+            },
+            inliers,
+        })
+    } else {
+        None
+    }
+}
+
+/// Picks the link to use out of the candidate links from one sensor onto sensors which have
+/// already been placed. Every candidate implies a pose for the sensor, and each one is
+/// supported by the inliers of all candidates whose poses agree with it (the same rotation and
+/// a position within 2 epsilon). The most supported candidate is chosen, and those which
+/// disagree with it are returned as rejected.
+pub fn choose_by_consensus<'a>(
+    candidates: Vec<ScoredLink<'a>>,
+    poses: &HashMap<usize, ScannerPose>,
+    epsilon: i64,
+) -> Option<(ScoredLink<'a>, Vec<ScoredLink<'a>>)> {
+    let implied = candidates
+        .iter()
+        .map(|scored| {
+            let link = &scored.link;
+            poses[&link.destination.id].chain(link.source.id, link.rotation, &link.translation)
+        })
+        .collect::<Vec<_>>();
+    let agree = |pose_1: &ScannerPose, pose_2: &ScannerPose| {
+        pose_1.rotation == pose_2.rotation
+            && pose_1.translation.chebyshev_distance(&pose_2.translation) <= 2 * epsilon
+    };
+    let chosen = implied
+        .iter()
+        .enumerate()
+        .map(|(idx, pose)| {
+            let support = implied
+                .iter()
+                .zip(&candidates)
+                .filter(|(other, _)| agree(pose, other))
+                .map(|(_, scored)| scored.inliers)
+                .sum::<usize>();
+            (idx, support, candidates[idx].inliers)
+        })
+        .min_by_key(|(_, support, inliers)| Reverse((*support, *inliers)))?
+        .0;
+    let chosen_pose = implied[chosen].clone();
+    let mut chosen_link = None;
+    let mut rejected = vec![];
+    candidates
+        .into_iter()
+        .zip(implied)
+        .enumerate()
+        .for_each(|(idx, (scored, pose))| {
+            if idx == chosen {
+                chosen_link = Some(scored);
+            } else if !agree(&chosen_pose, &pose) {
+                rejected.push(scored);
+            }
+        });
+    Some((chosen_link?, rejected))
+}
+
+/// Merges points which are within epsilon of an earlier point along every axis, keeping the
+/// earlier point. The result is sorted.
+pub fn cluster_points(
+    points: impl Iterator<Item = Vector<i64, 3>>,
+    epsilon: i64,
+) -> Vec<Vector<i64, 3>> {
+    let mut clusters: Vec<Vector<i64, 3>> = vec![];
+    points.for_each(|point| {
+        if !clusters
+            .iter()
+            .any(|cluster| cluster.chebyshev_distance(&point) <= epsilon)
+        {
+            clusters.push(point);
+        }
+    });
+    clusters.sort_unstable();
+    clusters
+}
+
+/// Finds the pairs of (source, destination) point indices which could be the same point, in the
+/// same way as the fingerprint index does, except that distances vote for each other if they're
+/// within the amount the metric could change when the points move by epsilon.
+fn tolerant_correspondences(
+    source: &Sensor,
+    destination: &Sensor,
+    metric: &DistanceMetric,
+    epsilon: i64,
+    minimum_overlap: usize,
+) -> Vec<(usize, usize)> {
+    let columns = destination.positions.len();
+    let mut votes = vec![0; source.positions.len() * columns];
+    source.distances.iter().for_each(|(distance, i, j)| {
+        let tolerance = metric.tolerance(*distance, epsilon);
+        let start = destination
+            .distances
+            .partition_point(|(other, ..)| *other < distance - tolerance);
+        destination.distances[start..]
+            .iter()
+            .take_while(|(other, ..)| *other <= distance + tolerance)
+            .for_each(|(_, k, l)| {
+                [(i, k), (i, l), (j, k), (j, l)].into_iter().for_each(
+                    |(source_idx, destination_idx)| {
+                        votes[source_idx * columns + destination_idx] += 1;
+                    },
+                );
+            });
+    });
+    let required = minimum_overlap.saturating_sub(1);
+    votes
+        .into_iter()
+        .enumerate()
+        .filter(|(_, count)| *count >= required)
+        .map(|(idx, _)| (idx / columns, idx % columns))
+        .collect()
+}
+
+/// Counts the source points which are within epsilon of a destination point once translated.
+fn count_inliers(
+    source: &[Vector<i64, 3>],
+    destination: &[Vector<i64, 3>],
+    translation: &Vector<i64, 3>,
+    epsilon: i64,
+) -> usize {
+    source
+        .iter()
+        .filter(|position| {
+            let position = **position + *translation;
+            destination
+                .iter()
+                .any(|point| position.chebyshev_distance(point) <= epsilon)
+        })
+        .count()
+}
+
+/// Takes the median along each axis of the offsets from each inlier to the destination point
+/// closest to it, which is less affected by the noise in any single point.
+fn refine_translation(
+    source: &[Vector<i64, 3>],
+    destination: &[Vector<i64, 3>],
+    translation: &Vector<i64, 3>,
+    epsilon: i64,
+) -> Vector<i64, 3> {
+    let offsets = source
+        .iter()
+        .filter_map(|position| {
+            let translated = *position + *translation;
+            let closest = destination
+                .iter()
+                .min_by_key(|point| translated.chebyshev_distance(point))?;
+            if translated.chebyshev_distance(closest) <= epsilon {
+                Some(*closest - *position)
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    if offsets.is_empty() {
+        return *translation;
+    }
+    let median = |axis: usize| {
+        let mut values = offsets
+            .iter()
+            .map(|offset| offset[axis])
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        values[values.len() / 2]
+    };
+    Vector::new([median(0), median(1), median(2)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::RotationGroup, *};
+
+    #[test]
+    fn test_cluster_points() {
+        let points = [
+            Vector::new([10, 0, 0]),
+            Vector::new([0, 0, 0]),
+            Vector::new([1, -1, 0]),
+            Vector::new([9, 1, 1]),
+            Vector::new([5, 5, 5]),
+        ];
+        assert_eq!(
+            cluster_points(points.into_iter(), 1),
+            vec![
+                Vector::new([0, 0, 0]),
+                Vector::new([5, 5, 5]),
+                Vector::new([10, 0, 0])
+            ]
+        );
+        assert_eq!(cluster_points(points.into_iter(), 0).len(), 5);
+    }
+
+    #[test]
+    fn test_refine_translation() {
+        let source = [
+            Vector::new([0, 0, 0]),
+            Vector::new([10, 0, 0]),
+            Vector::new([0, 10, 0]),
+            Vector::new([100, 100, 100]),
+        ];
+        let destination = [
+            Vector::new([5, 5, 6]),
+            Vector::new([15, 4, 5]),
+            Vector::new([4, 15, 5]),
+        ];
+        assert_eq!(
+            refine_translation(&source, &destination, &Vector::new([5, 5, 6]), 2),
+            Vector::new([5, 5, 5])
+        );
+    }
+
+    #[test]
+    fn test_score_link_with_noise_and_strays() {
+        let metric = DistanceMetric::default();
+        let points = [
+            Vector::new([0, 0, 0]),
+            Vector::new([40, 3, -7]),
+            Vector::new([-12, 25, 9]),
+            Vector::new([31, -18, 22]),
+            Vector::new([8, 8, -30]),
+        ];
+        let source = Sensor::new(0, points.to_vec(), &metric);
+        let jitter = [
+            Vector::new([1, 0, -1]),
+            Vector::new([0, 1, 0]),
+            Vector::new([-1, -1, 1]),
+            Vector::new([1, 1, 0]),
+            Vector::new([0, 0, 0]),
+        ];
+        let destination = Sensor::new(
+            1,
+            points
+                .iter()
+                .zip(jitter)
+                .map(|(point, jitter)| *point + Vector::new([100, 0, -50]) + jitter)
+                .chain([Vector::new([500, 500, 500]), Vector::new([-80, 20, 7])])
+                .collect(),
+            &metric,
+        );
+        let rotations = RotationGroup::Proper.matrices();
+        let config = ScannerSolverConfig {
+            minimum_overlap: 5,
+            ..Default::default()
+        };
+        assert!(score_link(&source, &destination, &rotations, &config, 0).is_none());
+        let scored = score_link(&source, &destination, &rotations, &config, 1).unwrap();
+        assert_eq!(scored.inliers, 5);
+        assert_eq!(*scored.link.rotation, rotations[0]);
+        assert_eq!(scored.link.translation, Vector::new([100, 0, -50]));
+    }
+
+    #[test]
+    fn test_choose_by_consensus() {
+        let metric = DistanceMetric::default();
+        let sensors = (0..4)
+            .map(|id| Sensor::new(id, vec![], &metric))
+            .collect::<Vec<_>>();
+        let identity = RotationGroup::Proper.matrices().remove(0);
+        let mut poses = HashMap::new();
+        poses.insert(0, ScannerPose::origin(0));
+        poses.insert(
+            1,
+            ScannerPose::origin(1).chain(1, &identity, &Vector::new([50, 0, 0])),
+        );
+        poses.insert(
+            2,
+            ScannerPose::origin(2).chain(2, &identity, &Vector::new([0, 50, 0])),
+        );
+        let link = |destination: usize, translation, inliers| ScoredLink {
+            link: SensorLink {
+                source: &sensors[3],
+                destination: &sensors[destination],
+                rotation: &identity,
+                translation,
+            },
+            inliers,
+        };
+        // Sensors 0 and 1 agree that sensor 3 is at (100, 0, 0) within tolerance. The link to
+        // sensor 2 has the most inliers on its own but puts it somewhere else entirely.
+        let candidates = vec![
+            link(0, Vector::new([100, 0, 0]), 12),
+            link(1, Vector::new([51, 1, 0]), 12),
+            link(2, Vector::new([0, 0, 0]), 20),
+        ];
+        let (chosen, rejected) = choose_by_consensus(candidates, &poses, 1).unwrap();
+        assert_eq!(chosen.link.destination.id, 0);
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].link.destination.id, 2);
+        assert!(choose_by_consensus(vec![], &poses, 1).is_none());
+    }
+}
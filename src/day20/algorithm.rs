@@ -1,36 +1,47 @@
 use {
-    super::Image,
+    super::{Image, Kernel},
     std::{convert::Infallible, str::FromStr},
 };
 
-/// Structure representing the 512 bit image enhancement algorithm
-/// which is used on the grid to enhance the image.
+/// Structure representing the image enhancement algorithm which is used on the grid to
+/// enhance the image. The puzzle uses a 3x3 kernel and so has 512 rules, but any kernel can be
+/// used with a rule for every combination of the pixels it covers. The rules are packed as bits
+/// and stored in the order of a RowMajor index, so that the image doesn't need to know which
+/// order the kernel actually reads its pixels in.
 #[derive(Debug, PartialEq)]
-pub struct Algorithm([bool; 512]);
+pub struct Algorithm {
+    kernel: Kernel,
+    table: Vec<u64>,
+}
 
 impl Algorithm {
+    /// Builds the rule table for the given kernel by calling the function with every possible
+    /// index (built in the kernel's bit order) to decide whether that pixel is on.
+    pub fn from_fn(kernel: Kernel, rule: impl Fn(usize) -> bool) -> Self {
+        let (size, bit_order) = (kernel.size(), kernel.bit_order());
+        let rules = 1usize << kernel.cells();
+        let mut table = vec![0; rules.div_ceil(64)];
+        (0..rules)
+            .filter(|index| rule(bit_order.reorder(*index, size)))
+            .for_each(|index| table[index / 64] |= 1 << (index % 64));
+        Self { kernel, table }
+    }
+
+    /// Gets the kernel which this algorithm's rules are for.
+    pub fn kernel(&self) -> Kernel {
+        self.kernel
+    }
+
+    /// Looks up the rule for the given index, where the index has been built by reading the
+    /// pixels of the kernel in RowMajor order.
+    pub fn lookup(&self, index: usize) -> bool {
+        self.table[index / 64] >> (index % 64) & 1 == 1
+    }
+
     /// Applies this enhancement algorithm to a given image to produce a new
     /// image.
     pub fn apply(&self, image: &Image) -> Image {
-        let mut output = (*image).clone(); // clone image as a starting point (to get correct dimensions)
-        output.add_borders();
-
-        // On an infinite image, if we parse a pixel that is entirely using the "out of bounds" value then it's either
-        // 0 (all off) or 512 (all on). We can therefore easily decide what the rest of the pixels on the infinite sheet will be.
-        output.current_out_of_bounds_pixel_value = if output.current_out_of_bounds_pixel_value {
-            self.0[511]
-        } else {
-            self.0[0]
-        };
-
-        output.pixels_mut().for_each(|(x, y, elem)| {
-            // remap x and y from {0, image.width+2} range to {-1, image.width+1} range.
-            // this just means subtracting 1.
-            let x = (x as isize) - 1;
-            let y = (y as isize) - 1;
-            *elem = self.0[image.calculate_algorithm_index(x, y)];
-        });
-        output
+        image.enhance(&self.kernel, |index| self.lookup(index))
     }
 }
 
@@ -51,7 +62,7 @@ impl FromStr for Algorithm {
             .for_each(|(idx, elem)| {
                 arr[idx] = elem;
             });
-        Ok(Self(arr))
+        Ok(Self::from_fn(Kernel::default(), |idx| arr[idx]))
     }
 }
 
@@ -83,7 +94,7 @@ mod tests {
                 arr[idx] = elem;
             });
         let calculated = INPUT.parse::<Algorithm>().unwrap();
-        let expected = Algorithm(arr);
+        let expected = Algorithm::from_fn(Kernel::default(), |idx| arr[idx]);
         assert_eq!(calculated, expected);
     }
 
@@ -120,6 +131,8 @@ mod tests {
         "
         .parse::<Image>()
         .unwrap();
-        assert_eq!(algorithm.apply(&image), output);
+        // the output has grown by a pixel on each side, so it's at a different position on the
+        // plane to the expected image parsed from a string. Only compare the pixels.
+        assert_eq!(algorithm.apply(&image).to_string(), output.to_string());
     }
 }
@@ -0,0 +1,70 @@
+/// The order that the pixels of a kernel are read in to build the index into the rule table.
+/// The first pixel read becomes the most significant bit of the index.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BitOrder {
+    /// Left to right along each row, from the top row down. This is what the puzzle uses.
+    #[default]
+    RowMajor,
+    /// The reverse of RowMajor, so the top left pixel is the least significant bit.
+    RowMajorReversed,
+    /// Top to bottom down each column, from the left column across.
+    ColumnMajor,
+    /// The reverse of ColumnMajor, so the top left pixel is the least significant bit.
+    ColumnMajorReversed,
+}
+
+impl BitOrder {
+    /// Gets which bit of the index the pixel at the given row and column of a square kernel of
+    /// the given size is placed in.
+    pub fn bit(&self, row: usize, col: usize, size: usize) -> usize {
+        let cells = size * size;
+        match self {
+            Self::RowMajor => cells - 1 - (row * size + col),
+            Self::RowMajorReversed => row * size + col,
+            Self::ColumnMajor => cells - 1 - (col * size + row),
+            Self::ColumnMajorReversed => col * size + row,
+        }
+    }
+
+    /// Takes an index built in RowMajor order and moves each of its bits to where this order
+    /// would have placed it.
+    pub fn reorder(&self, index: usize, size: usize) -> usize {
+        if let Self::RowMajor = self {
+            return index;
+        }
+        (0..size)
+            .flat_map(|row| (0..size).map(move |col| (row, col)))
+            .filter(|(row, col)| index >> BitOrder::RowMajor.bit(*row, *col, size) & 1 == 1)
+            .fold(0, |reordered, (row, col)| {
+                reordered | 1 << self.bit(row, col, size)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit() {
+        assert_eq!(BitOrder::RowMajor.bit(0, 0, 3), 8);
+        assert_eq!(BitOrder::RowMajor.bit(1, 2, 3), 3);
+        assert_eq!(BitOrder::RowMajorReversed.bit(1, 2, 3), 5);
+        assert_eq!(BitOrder::ColumnMajor.bit(1, 2, 3), 1);
+        assert_eq!(BitOrder::ColumnMajorReversed.bit(1, 2, 3), 7);
+    }
+
+    #[test]
+    fn test_reorder() {
+        // top left and middle right pixels on.
+        let index = 0b100_001_000;
+        assert_eq!(BitOrder::RowMajor.reorder(index, 3), index);
+        assert_eq!(BitOrder::RowMajorReversed.reorder(index, 3), 0b000_100_001);
+        assert_eq!(BitOrder::ColumnMajor.reorder(index, 3), 0b100_000_010);
+        assert_eq!(
+            BitOrder::ColumnMajorReversed.reorder(index, 3),
+            0b010_000_001
+        );
+        assert_eq!(BitOrder::ColumnMajor.reorder(511, 3), 511);
+    }
+}
@@ -1,151 +1,271 @@
-use std::{convert::Infallible, iter::repeat, str::FromStr};
+use {
+    super::Kernel,
+    crate::common::{math::Vector, netpbm::Bitmap},
+    std::{
+        convert::Infallible,
+        fmt::{Display, Formatter},
+        str::FromStr,
+    },
+};
 
-/// Represents the 2D grid of pixels. We'll just store this as
-/// an actual 2D grid, rather than flattening it, since we're going
-/// to want to access by x/y coordinate anyway.
+// number of pixels packed into each word of a row.
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Represents an infinite plane of pixels. Only the pixels inside a bounding box are stored,
+/// packed as bits into rows of words, and every pixel outside of the box has the same
+/// background value. Coordinates are relative to the top left of the original input image
+/// and can go negative as the image grows.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Image {
-    grid: Vec<Vec<bool>>,
+    words: Vec<u64>,
+    stride: usize,
+    top_left: Vector<i64, 2>,
     width: usize,
     height: usize,
-    pub current_out_of_bounds_pixel_value: bool,
+    background: bool,
 }
 
 impl Image {
-    /// Provides an iterator over the pixels of the image providing mutable access.
-    pub fn pixels_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut bool)> {
-        self.grid.iter_mut().enumerate().flat_map(|(y, row)| {
-            row.iter_mut()
-                .enumerate()
-                .map(move |(x, elem)| (x, y, elem))
-        })
+    /// Constructs a new image where every pixel in the bounding box is off.
+    fn blank(top_left: Vector<i64, 2>, width: usize, height: usize, background: bool) -> Self {
+        let stride = width.div_ceil(WORD_BITS);
+        Self {
+            words: vec![0; stride * height],
+            stride,
+            top_left,
+            width,
+            height,
+            background,
+        }
     }
 
-    /// Provides an iterator over the pixels of the image providing immutable access.
-    pub fn pixels(&self) -> impl Iterator<Item = (usize, usize, &bool)> {
-        self.grid
-            .iter()
-            .enumerate()
-            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, elem)| (x, y, elem)))
+    /// Constructs a new image from the pixels of the bitmap, with the background off.
+    pub fn from_bitmap(bitmap: &impl Bitmap) -> Self {
+        let (width, height) = (bitmap.width(), bitmap.height());
+        let mut image = Self::blank(Vector::new([0, 0]), width, height, false);
+        (0..height)
+            .flat_map(|row| (0..width).map(move |col| (col, row)))
+            .filter(|(col, row)| bitmap.pixel(*col, *row))
+            .for_each(|(col, row)| image.set(col, row));
+        image
     }
 
-    /// Since the output image is infinite, we need to account for the
-    /// setting of pixels which are off the edges of the input image. Since the
-    /// algorithm works in a 3x3 grid around a given pixel, we only need a 1 pixel
-    /// wide border.
-    pub fn add_borders(&mut self) {
-        self.width += 2;
-        self.height += 2;
-        self.grid.iter_mut().for_each(|line| {
-            line.insert(0, self.current_out_of_bounds_pixel_value);
-            line.insert(line.len(), self.current_out_of_bounds_pixel_value);
-        });
-        self.grid.insert(
-            0,
-            repeat(self.current_out_of_bounds_pixel_value)
-                .take(self.width)
-                .collect(),
-        );
-        self.grid.insert(
-            self.grid.len(),
-            repeat(self.current_out_of_bounds_pixel_value)
-                .take(self.width)
-                .collect(),
-        );
+    /// Counts the pixels which are on, or None if the background is on since then there are
+    /// infinitely many.
+    pub fn count_lit(&self) -> Option<usize> {
+        if self.background {
+            None
+        } else {
+            Some(
+                self.words
+                    .iter()
+                    .map(|word| word.count_ones() as usize)
+                    .sum(),
+            )
+        }
     }
 
-    /// Takes an input coordinate, where (0, 0) is the top left of the input image
-    /// before getting to the infinite empty space. Note that the coordinate can go negative
-    /// as the output image will be calculating pixels that are in the border of the image
-    /// that doesn't exist in the input image.
-    pub fn calculate_algorithm_index(&self, x: isize, y: isize) -> usize {
-        (self.calculate_pixel_value(x - 1, y - 1) << 8)
-            + (self.calculate_pixel_value(x, y - 1) << 7)
-            + (self.calculate_pixel_value(x + 1, y - 1) << 6)
-            + (self.calculate_pixel_value(x - 1, y) << 5)
-            + (self.calculate_pixel_value(x, y) << 4)
-            + (self.calculate_pixel_value(x + 1, y) << 3)
-            + (self.calculate_pixel_value(x - 1, y + 1) << 2)
-            + (self.calculate_pixel_value(x, y + 1) << 1)
-            + self.calculate_pixel_value(x + 1, y + 1)
+    /// Produces the next image by building the index of the square of pixels covered by the
+    /// kernel around each pixel, and passing it to the rule to decide whether the pixel is on.
+    /// The bounding box grows by the radius of the kernel on each side, and the pixels outside
+    /// it all share the same index (all off or all on) so the new background is just the rule
+    /// for that index.
+    ///
+    /// The index is always built in RowMajor order. Each row is a single sweep from left to
+    /// right, keeping the index as a rolling window so that moving along a pixel only needs
+    /// the next column of pixels to be read in.
+    pub fn enhance(&self, kernel: &Kernel, rule: impl Fn(usize) -> bool) -> Self {
+        let size = kernel.size();
+        let radius = kernel.radius() as i64;
+        let background_index = if self.background {
+            (1 << kernel.cells()) - 1
+        } else {
+            0
+        };
+        let mut output = Self::blank(
+            self.top_left - Vector::new([radius, radius]),
+            self.width + 2 * radius as usize,
+            self.height + 2 * radius as usize,
+            rule(background_index),
+        );
+        // After shifting the window along by a column, the lowest bit of each row is where
+        // the new column goes so those are cleared, along with anything shifted off the top.
+        let keep_mask = (0..size).fold((1 << kernel.cells()) - 1, |mask, row| {
+            mask & !(1 << (row * size))
+        });
+        (0..output.height).for_each(|out_row| {
+            // output pixels are offset by the radius from the input pixels they're centered on.
+            let row = out_row as i64 - radius;
+            let column = |col: i64| {
+                (0..size).fold(0, |bits, offset| {
+                    let pixel = self.pixel(col, row - radius + offset as i64) as usize;
+                    bits | pixel << ((size - 1 - offset) * size)
+                })
+            };
+            let mut window =
+                (-2 * radius..0).fold(0, |window, col| (window << 1) & keep_mask | column(col));
+            (0..output.width).for_each(|out_col| {
+                window = (window << 1) & keep_mask | column(out_col as i64);
+                if rule(window) {
+                    output.set(out_col, out_row);
+                }
+            });
+        });
+        output
     }
 
-    /// Calculates the value of the single pixel at the given coordinates. If the coordinate
-    /// is off the image then it will be the current out of bounds pixel value.
-    /// If it's on the image, it'll be whatever the value of the pixel is at that coordinate.
-    fn calculate_pixel_value(&self, x: isize, y: isize) -> usize {
-        if x < 0 || x as usize >= self.width || y < 0 || y as usize >= self.height {
-            self.current_out_of_bounds_pixel_value as usize
+    // Gets the pixel at the given column and row relative to the top left of the bounding box,
+    // falling back to the background if it's outside.
+    fn pixel(&self, col: i64, row: i64) -> bool {
+        if col < 0 || row < 0 || col as usize >= self.width || row as usize >= self.height {
+            self.background
         } else {
-            self.grid[y as usize][x as usize] as usize
+            self.bit(col as usize, row as usize)
         }
     }
+
+    fn bit(&self, col: usize, row: usize) -> bool {
+        self.words[row * self.stride + col / WORD_BITS] >> (col % WORD_BITS) & 1 == 1
+    }
+
+    fn set(&mut self, col: usize, row: usize) {
+        self.words[row * self.stride + col / WORD_BITS] |= 1 << (col % WORD_BITS);
+    }
 }
 
 impl FromStr for Image {
     type Err = Infallible;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let mut grid = vec![];
-        let mut width = 0;
+        let lines = string
+            .trim()
+            .lines()
+            .map(|line| line.trim())
+            .collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.len());
+        let mut image = Self::blank(Vector::new([0, 0]), width, lines.len(), false);
 
-        string.trim().lines().for_each(|line| {
-            let line = line.trim();
+        lines.into_iter().enumerate().for_each(|(row, line)| {
             let line_len = line.len();
-            if width != 0 && line_len != width {
+            if line_len != width {
                 panic!(
                     "Input has unequal line lengths. Expected line length {}, found line length {}",
                     width, line_len
                 );
             }
-            width = line_len;
-            grid.push(
-                line.chars()
-                    .map(|c| match c {
-                        '#' => true,
-                        '.' => false,
-                        _ => panic!("Invalid character '{}' found in input string.", c),
-                    })
-                    .collect(),
-            );
+            line.chars().enumerate().for_each(|(col, c)| match c {
+                '#' => image.set(col, row),
+                '.' => {}
+                _ => panic!("Invalid character '{}' found in input string.", c),
+            });
         });
-        let height = grid.len();
 
-        Ok(Self {
-            grid,
-            width,
-            height,
-            current_out_of_bounds_pixel_value: false,
+        Ok(image)
+    }
+}
+
+// Only the pixels inside the bounding box are written, so the background isn't included.
+impl Bitmap for Image {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, col: usize, row: usize) -> bool {
+        self.bit(col, row)
+    }
+}
+
+// Writes the pixels inside the bounding box using the same characters as the input.
+impl Display for Image {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        (0..self.height).try_for_each(|row| {
+            (0..self.width)
+                .try_for_each(|col| write!(f, "{}", if self.bit(col, row) { '#' } else { '.' }))?;
+            writeln!(f)
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {
+        super::*,
+        crate::{
+            common::{netpbm::NetpbmImage, ocr::read_text},
+            day20::BitOrder,
+        },
+    };
 
-    #[test]
-    fn test_image_from_str() {
-        const INPUT: &str = "
+    const INPUT: &str = "
         #..#.
         #....
         ##..#
         ..#..
         ..###";
-        let expected = Image {
-            grid: vec![
-                vec![true, false, false, true, false],
-                vec![true, false, false, false, false],
-                vec![true, true, false, false, true],
-                vec![false, false, true, false, false],
-                vec![false, false, true, true, true],
-            ],
-            width: 5,
-            height: 5,
-            current_out_of_bounds_pixel_value: false,
-        };
-        let calculated = INPUT.parse::<Image>().unwrap();
-        assert_eq!(calculated, expected);
+
+    // The lookup table from the puzzle example.
+    fn example_table() -> [bool; 512] {
+        let mut table = [false; 512];
+        "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..##\
+        #..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###\
+        .######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#.\
+        .#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#.....\
+        .#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#..\
+        ...####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.....\
+        ..##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#"
+            .chars()
+            .enumerate()
+            .for_each(|(idx, c)| table[idx] = c == '#');
+        table
+    }
+
+    // Enhances by reading every pixel of the kernel for every output pixel, which is what
+    // the rolling window is checked against.
+    fn enhance_naive(image: &Image, size: usize, rule: impl Fn(usize) -> bool) -> Image {
+        let radius = (size / 2) as i64;
+        let mut output = Image::blank(
+            image.top_left - Vector::new([radius, radius]),
+            image.width + 2 * radius as usize,
+            image.height + 2 * radius as usize,
+            rule(if image.background {
+                (1 << (size * size)) - 1
+            } else {
+                0
+            }),
+        );
+        (0..output.height).for_each(|row| {
+            (0..output.width).for_each(|col| {
+                let (x, y) = (
+                    output.top_left.x() + col as i64 - image.top_left.x(),
+                    output.top_left.y() + row as i64 - image.top_left.y(),
+                );
+                let index = (-radius..=radius)
+                    .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+                    .fold(0, |index, (dx, dy)| {
+                        index << 1 | image.pixel(x + dx, y + dy) as usize
+                    });
+                if rule(index) {
+                    output.set(col, row);
+                }
+            });
+        });
+        output
+    }
+
+    #[test]
+    fn test_image_from_str() {
+        let image = INPUT.parse::<Image>().unwrap();
+        assert_eq!((image.width, image.height, image.stride), (5, 5, 1));
+        assert_eq!(image.top_left, Vector::new([0, 0]));
+        assert!(!image.background);
+        assert_eq!(
+            image.words,
+            vec![0b01001, 0b00001, 0b10011, 0b00100, 0b11100]
+        );
     }
 
     #[test]
@@ -173,101 +293,109 @@ mod tests {
     }
 
     #[test]
-    fn test_image_add_borders() {
-        let mut input = r#"
-        #..#.
-        #....
-        ##..#
-        ..#..
-        ..###
-        "#
-        .parse::<Image>()
-        .unwrap();
-        let expected = r#"
-        .......
-        .#..#..
-        .#.....
-        .##..#.
-        ...#...
-        ...###.
-        .......
-        "#
-        .parse::<Image>()
-        .unwrap();
-        input.add_borders();
-        assert_eq!(input, expected);
+    fn test_image_display() {
+        let image = INPUT.parse::<Image>().unwrap();
+        assert_eq!(image.to_string(), "#..#.\n#....\n##..#\n..#..\n..###\n");
+        assert_eq!(image.to_string().parse::<Image>().unwrap(), image);
     }
 
     #[test]
-    fn test_calculate_pixel_value() {
-        let input = r#"
-        #..#.
-        #....
-        ##..#
-        ..#..
-        ..###
-        "#
-        .parse::<Image>()
-        .unwrap();
-        assert_eq!(input.calculate_pixel_value(-1, 3), 0);
-        assert_eq!(input.calculate_pixel_value(5, 2), 0);
-        assert_eq!(input.calculate_pixel_value(0, -1), 0);
-        assert_eq!(input.calculate_pixel_value(0, 5), 0);
-        assert_eq!(input.calculate_pixel_value(0, 2), 1);
-        assert_eq!(input.calculate_pixel_value(0, 3), 0);
+    fn test_image_pbm_round_trip() {
+        let image = INPUT.parse::<Image>().unwrap();
+        let mut output = vec![];
+        image.write_pbm(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output,
+            "P1\n5 5\n1 0 0 1 0\n1 0 0 0 0\n1 1 0 0 1\n0 0 1 0 0\n0 0 1 1 1\n"
+        );
+        let netpbm = output.parse::<NetpbmImage>().unwrap();
+        assert_eq!(Image::from_bitmap(&netpbm), image);
     }
 
     #[test]
-    fn test_calculate_algorithm_index() {
-        let input = r#"
-        #..#.
-        #....
-        ##..#
-        ..#..
-        ..###
-        "#
-        .parse::<Image>()
-        .unwrap();
-        const EXPECTED: usize = 34;
-        assert_eq!(input.calculate_algorithm_index(2, 2), EXPECTED);
+    fn test_image_read_text() {
+        let image = "
+            ...........
+            .#..#...##.
+            .#..#....#.
+            .####....#.
+            .#..#....#.
+            .#..#.#..#.
+            .#..#..##..
+            ..........."
+            .parse::<Image>()
+            .unwrap();
+        assert_eq!(read_text(&image), Ok("HJ".to_string()));
     }
 
     #[test]
-    fn test_pixels_mut() {
-        let mut input = r#"
-        #..#.
-        #....
-        ##..#
-        ..#..
-        ..###
-        "#
-        .parse::<Image>()
-        .unwrap();
-        let expected = r#"
-        .##.#
-        .####
-        ..##.
-        ##.##
-        ##...
-        "#
-        .parse::<Image>()
-        .unwrap();
-        input.pixels_mut().for_each(|(_, _, elem)| *elem = !*elem);
-        assert_eq!(input, expected);
+    fn test_pixel() {
+        let input = INPUT.parse::<Image>().unwrap();
+        assert!(!input.pixel(-1, 3));
+        assert!(!input.pixel(5, 2));
+        assert!(!input.pixel(0, -1));
+        assert!(!input.pixel(0, 5));
+        assert!(input.pixel(0, 2));
+        assert!(!input.pixel(0, 3));
     }
 
     #[test]
-    fn test_pixels() {
-        let input = r#"
-        #..#.
-        #....
-        ##..#
-        ..#..
-        ..###
-        "#
-        .parse::<Image>()
-        .unwrap();
-        let count = input.pixels().filter(|(_, _, elem)| **elem).count();
-        assert_eq!(count, 10);
+    fn test_count_lit() {
+        let mut image = INPUT.parse::<Image>().unwrap();
+        assert_eq!(image.count_lit(), Some(10));
+        image.background = true;
+        assert_eq!(image.count_lit(), None);
+    }
+
+    #[test]
+    fn test_enhance_matches_naive() {
+        let table = example_table();
+        let mut flashing = table;
+        flashing[0] = true;
+        flashing[511] = false;
+        [table, flashing].into_iter().for_each(|table| {
+            let rule = |index: usize| table[index];
+            let mut image = INPUT.parse::<Image>().unwrap();
+            (0..6).for_each(|_| {
+                let expected = enhance_naive(&image, 3, rule);
+                image = image.enhance(&Kernel::default(), rule);
+                assert_eq!(image, expected);
+            });
+        });
+    }
+
+    #[test]
+    fn test_enhance_wide_rows() {
+        // rows wider than a word, so the window has to cross between words.
+        let table = example_table();
+        let row = (0..150)
+            .map(|idx| if idx % 7 < 3 { '#' } else { '.' })
+            .collect::<String>();
+        let image = [row.as_str(); 3].join("\n").parse::<Image>().unwrap();
+        assert_eq!(image.stride, 3);
+        let rule = |index: usize| table[index];
+        assert_eq!(
+            image.enhance(&Kernel::default(), rule),
+            enhance_naive(&image, 3, rule)
+        );
+    }
+
+    #[test]
+    fn test_enhance_other_kernel_sizes() {
+        // an even number of pixels on turns the background on, and then off again since 25
+        // is odd.
+        let rule = |index: usize| index.count_ones().is_multiple_of(2);
+        [1, 5].into_iter().for_each(|size| {
+            let kernel = Kernel::new(size, BitOrder::RowMajor);
+            let mut image = INPUT.parse::<Image>().unwrap();
+            (0..3).for_each(|pass| {
+                let expected = enhance_naive(&image, size, rule);
+                image = image.enhance(&kernel, rule);
+                assert_eq!(image, expected);
+                assert_eq!(image.background, pass % 2 == 0);
+            });
+            assert_eq!(image.width, 5 + 3 * 2 * (size / 2));
+        });
     }
 }
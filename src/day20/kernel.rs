@@ -0,0 +1,81 @@
+use super::BitOrder;
+
+/// The square of pixels around each pixel which is used to decide its value in the next image,
+/// along with the order the pixels are read in to build the index into the rule table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Kernel {
+    size: usize,
+    bit_order: BitOrder,
+}
+
+impl Kernel {
+    /// The largest number of pixels a kernel can have, which keeps the rule table (with one bit
+    /// for every combination of pixels) at a few megabytes.
+    pub const MAX_CELLS: usize = 25;
+
+    /// Constructs a new kernel of the given size, which needs to be odd so that the kernel
+    /// has a center pixel.
+    pub fn new(size: usize, bit_order: BitOrder) -> Self {
+        assert!(size % 2 == 1, "Kernel size must be odd, found {}", size);
+        assert!(
+            size * size <= Self::MAX_CELLS,
+            "Kernel size {} has more than {} pixels",
+            size,
+            Self::MAX_CELLS
+        );
+        Self { size, bit_order }
+    }
+
+    /// Gets the width and height of the kernel.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Gets how far the kernel reaches from its center pixel, which is also how much the
+    /// bounding box of the image grows on each side by each enhancement.
+    pub fn radius(&self) -> usize {
+        self.size / 2
+    }
+
+    /// Gets the number of pixels covered by the kernel.
+    pub fn cells(&self) -> usize {
+        self.size * self.size
+    }
+
+    /// Gets the order the pixels are read in to build the index into the rule table.
+    pub fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+}
+
+// The 3x3 kernel read in row major order, as used by the puzzle.
+impl Default for Kernel {
+    fn default() -> Self {
+        Self::new(3, BitOrder::RowMajor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernel() {
+        let kernel = Kernel::new(5, BitOrder::ColumnMajor);
+        assert_eq!((kernel.size(), kernel.radius(), kernel.cells()), (5, 2, 25));
+        assert_eq!(kernel.bit_order(), BitOrder::ColumnMajor);
+        assert_eq!(Kernel::default(), Kernel::new(3, BitOrder::RowMajor));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_kernel_even_size() {
+        Kernel::new(4, BitOrder::RowMajor);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_kernel_too_large() {
+        Kernel::new(7, BitOrder::RowMajor);
+    }
+}
@@ -1,9 +1,27 @@
 mod algorithm;
+mod bit_order;
 mod image;
+mod kernel;
 
-use {crate::common::iter::FilterGroupMapExt, algorithm::Algorithm, image::Image};
+use {crate::common::iter::FilterGroupMapExt, std::iter::successors};
+
+pub use {algorithm::Algorithm, bit_order::BitOrder, image::Image, kernel::Kernel};
 
 pub fn run(input: &str) -> (usize, usize) {
+    let mut frames = enhancement_frames(input);
+
+    // Apply the enhancement algorithm to the image twice.
+    let part_1 = frames.nth(2).unwrap().count_lit().unwrap();
+
+    // For part 2, we need to do another 48 times.
+    let part_2 = frames.nth(47).unwrap().count_lit().unwrap();
+
+    (part_1, part_2)
+}
+
+/// Parses the algorithm and image from the input, and then gives the original image followed
+/// by the result of each enhancement pass in turn. The iterator never ends.
+pub fn enhancement_frames(input: &str) -> impl Iterator<Item = Image> {
     // Regroup lines into two groups, one for the algorithm, and one for the image.
     let mut iter = input.trim().lines().filter_group_map(
         |line| !line.trim().is_empty(),
@@ -18,17 +36,9 @@ pub fn run(input: &str) -> (usize, usize) {
 
     // Parse the algorithm and image.
     let algorithm = iter.next().unwrap().parse::<Algorithm>().unwrap();
-    let mut image = iter.next().unwrap().parse::<Image>().unwrap();
-
-    // Apply the enhancement algorithm to the image twice.
-    (0..2).for_each(|_| image = algorithm.apply(&image));
-    let part_1 = image.pixels().filter(|(_, _, pixel)| **pixel).count();
+    let image = iter.next().unwrap().parse::<Image>().unwrap();
 
-    // For part 2, we need to do another 48 times.
-    (0..48).for_each(|_| image = algorithm.apply(&image));
-    let part_2 = image.pixels().filter(|(_, _, pixel)| **pixel).count();
-
-    (part_1, part_2)
+    successors(Some(image), move |image| Some(algorithm.apply(image)))
 }
 
 #[cfg(test)]
@@ -54,5 +64,8 @@ mod tests {
         ";
         const EXPECTED: (usize, usize) = (35, 3351);
         assert_eq!(run(INPUT), EXPECTED);
+
+        let first = enhancement_frames(INPUT).next().unwrap();
+        assert_eq!(first.to_string(), "#..#.\n#....\n##..#\n..#..\n..###\n");
     }
 }
@@ -1,3 +1,177 @@
-pub fn run(_input: &str) -> (u32, u32) {
-    (0, 0)
+use crate::common::cycles::find_cycle;
+
+const EMPTY: u8 = b'.';
+const EAST: u8 = b'>';
+const SOUTH: u8 = b'v';
+const MAX_CYCLE_STEPS: usize = 1_000_000;
+
+pub fn run(input: &str) -> (usize, usize) {
+    let sea_floor = SeaFloor::from(input);
+    let part_1 = steps_until_stopped(&sea_floor)
+        .expect("The sea cucumbers never stop moving, so there's nowhere to land.");
+    (part_1, 0)
+}
+
+/// The sea cucumbers on the sea floor, which wraps around at the edges.
+#[derive(Clone, Debug, PartialEq)]
+struct SeaFloor {
+    cells: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl SeaFloor {
+    /// Moves the east facing herd and then the south facing herd, where every sea cucumber
+    /// in a herd which has an empty space in front of it moves at the same time.
+    fn step(&mut self) {
+        self.move_herd(EAST, |x, y, width, _| ((x + 1) % width, y));
+        self.move_herd(SOUTH, |x, y, _, height| (x, (y + 1) % height));
+    }
+
+    fn move_herd(&mut self, herd: u8, next: impl Fn(usize, usize, usize, usize) -> (usize, usize)) {
+        let moves = (0..self.cells.len())
+            .filter_map(|idx| {
+                let (x, y) = next(idx % self.width, idx / self.width, self.width, self.height);
+                let next_idx = y * self.width + x;
+                (self.cells[idx] == herd && self.cells[next_idx] == EMPTY)
+                    .then_some((idx, next_idx))
+            })
+            .collect::<Vec<_>>();
+        moves.into_iter().for_each(|(idx, next_idx)| {
+            self.cells[idx] = EMPTY;
+            self.cells[next_idx] = herd;
+        });
+    }
+}
+
+/// Parses the sea floor from lines of '.', '>' and 'v'. Panics if there are any other
+/// characters or the lines aren't all the same length.
+impl From<&str> for SeaFloor {
+    fn from(string: &str) -> Self {
+        let lines = string
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.len());
+        let cells = lines
+            .iter()
+            .flat_map(|line| {
+                assert_eq!(
+                    line.len(),
+                    width,
+                    "Expected all lines to be the same length."
+                );
+                line.bytes()
+            })
+            .inspect(|cell| {
+                assert!(
+                    [EMPTY, EAST, SOUTH].contains(cell),
+                    "Invalid character {} found on the sea floor.",
+                    *cell as char
+                )
+            })
+            .collect();
+        Self {
+            cells,
+            width,
+            height: lines.len(),
+        }
+    }
+}
+
+/// Finds the first step on which no sea cucumbers move, giving None if they keep moving around
+/// forever (or the sea floor hasn't repeated within a million steps).
+fn steps_until_stopped(sea_floor: &SeaFloor) -> Option<usize> {
+    let cycle = find_cycle(
+        sea_floor,
+        SeaFloor::step,
+        |sea_floor| sea_floor.cells.clone(),
+        MAX_CYCLE_STEPS,
+    )?;
+    (cycle.period == 1).then_some(cycle.start + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let mut sea_floor = SeaFloor::from(
+            "
+            ..........
+            .>v....v..
+            .......>..
+            ..........",
+        );
+        sea_floor.step();
+        assert_eq!(
+            sea_floor,
+            SeaFloor::from(
+                "
+                ..........
+                .>........
+                ..v....v>.
+                .........."
+            )
+        );
+    }
+
+    #[test]
+    fn test_step_wrapping() {
+        let mut sea_floor = SeaFloor::from(
+            "
+            ...>...
+            .......
+            ......>
+            v.....>
+            ......>
+            .......
+            ..vvv..",
+        );
+        sea_floor.step();
+        assert_eq!(
+            sea_floor,
+            SeaFloor::from(
+                "
+                ..vv>..
+                .......
+                >......
+                v.....>
+                >......
+                .......
+                ....v.."
+            )
+        );
+    }
+
+    #[test]
+    fn test_steps_until_stopped() {
+        let sea_floor = SeaFloor::from(
+            "
+            v...>>.vv>
+            .vv>>.vv..
+            >>.>v>...v
+            >>v>>.>.v.
+            v>v.vv.v..
+            >.>>..v...
+            .vv..>.>v.
+            v.v..>>v.v
+            ....v..v.>",
+        );
+        assert_eq!(steps_until_stopped(&sea_floor), Some(58));
+    }
+
+    #[test]
+    fn test_steps_until_stopped_never() {
+        // A single sea cucumber in an otherwise empty row goes round and round.
+        assert_eq!(steps_until_stopped(&SeaFloor::from(">..")), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_invalid_character() {
+        let _ = SeaFloor::from("..>\n.x.");
+    }
 }
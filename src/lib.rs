@@ -25,6 +25,32 @@ mod day23;
 mod day24;
 mod day25;
 
+pub use common::{
+    graph::{ParseGraphError, UndirectedGraph},
+    netpbm::{Bitmap, Graymap, NetpbmImage, ParseNetpbmError},
+    ocr::{read_text, ReadTextError},
+};
+pub use day06::count_fish_modulo;
+pub use day10::{
+    calculate_scores as calculate_day_10_scores, BuildDelimiterTableError, DelimiterPair,
+    DelimiterTable, Edit, LineParser, LineType, Opener, Repair,
+};
+pub use day11::{EdgeBehaviour, Grid as OctopusGrid, GridConfig, Neighbourhood};
+pub use day12::{
+    CaveKind, CaveSystem, OneSmallCaveTwice, PathCounter, Paths, SmallCavesOnce, SmallCavesUpTo,
+    VisitPolicy,
+};
+pub use day13::{
+    parse_input as parse_day_13, Axis, BuildPaperError, Dot, FoldInstruction, FoldPaperError,
+    Paper, ParseFoldInstructionError, ParseInputError,
+};
+pub use day18::{largest_pairwise_magnitude_parallel, trace_addition, ParseSnailfishError};
+pub use day19::{
+    reconstruct_map, solve_with_config, AlignmentStrategy, DistanceMetric, MatchingMode,
+    RotationGroup, ScannerMap, ScannerPose, ScannerSolverConfig,
+};
+pub use day20::{enhancement_frames, Algorithm, BitOrder, Image, Kernel};
+
 use std::{fmt::Display, fs::read_to_string};
 
 pub fn run_with(day: usize, func: impl Fn(&dyn Display, &dyn Display)) {
@@ -65,7 +91,12 @@ fn call_with<'a, T: Display + 'a, U: Display + 'a>(
     day: usize,
     func: impl Fn(&dyn Display, &dyn Display) + 'a,
 ) {
-    let string = read_to_string(format!("input/day{:02}.txt", day)).unwrap();
+    let string = read_input(day);
     let tuple = run(&string);
     func(&tuple.0, &tuple.1)
 }
+
+/// Reads the puzzle input for the given day from the input directory.
+pub fn read_input(day: usize) -> String {
+    read_to_string(format!("input/day{:02}.txt", day)).unwrap()
+}
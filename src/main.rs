@@ -1,21 +1,120 @@
 use {
     aoc_2021::*,
-    std::{env::args, time::Instant},
+    std::{
+        env::args,
+        fs::File,
+        io::{stdout, BufWriter, Write},
+        path::Path,
+        time::Instant,
+    },
 };
 
 fn main() {
-    if let Some(solution_number) = args().skip(1).next() {
+    let mut args = args().skip(1);
+    if let Some(solution_number) = args.next() {
         let solution_number = solution_number
             .parse::<usize>()
             .expect("Failed to parse provided command line argument as a number.");
-        let start_time = Instant::now();
-        run_with(solution_number, |part_1, part_2| {
-            let elapsed = start_time.elapsed().as_micros();
-            println!("Part 1 => {}", part_1);
-            println!("Part 2 => {}", part_2);
-            println!("Took {} microseconds", elapsed);
-        });
+        match (solution_number, args.next().as_deref()) {
+            (10, Some("repair")) => repair_day_10(),
+            (12, Some("export")) => export_day_12(args),
+            (18, Some("trace")) => trace_day_18(args),
+            (19, Some("export")) => export_day_19(args),
+            (20, Some("frames")) => export_day_20_frames(args),
+            _ => run_timed(solution_number),
+        }
     } else {
         panic!("Argument not provided, should be run with the day number.");
     }
 }
+
+/// Runs the solution for the given day, printing both answers and how long it took.
+fn run_timed(solution_number: usize) {
+    let start_time = Instant::now();
+    run_with(solution_number, |part_1, part_2| {
+        let elapsed = start_time.elapsed().as_micros();
+        println!("Part 1 => {}", part_1);
+        println!("Part 2 => {}", part_2);
+        println!("Took {} microseconds", elapsed);
+    });
+}
+
+/// Prints the fewest edits which would complete each line of the day 10 input that isn't
+/// already complete.
+fn repair_day_10() {
+    let table = DelimiterTable::default();
+    read_input(10)
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, Repair::new(line.trim(), &table)))
+        .filter(|(_, repair)| !repair.edits.is_empty())
+        .for_each(|(line, repair)| {
+            let edits = repair
+                .edits
+                .iter()
+                .map(|edit| edit.to_string())
+                .collect::<Vec<_>>();
+            println!("Line {} => {}", line, edits.join(", "));
+        });
+}
+
+/// Writes the day 12 cave system to stdout in the format given as an argument.
+fn export_day_12(mut args: impl Iterator<Item = String>) {
+    let system = CaveSystem::from_links(read_input(12).trim().lines());
+    let mut writer = stdout().lock();
+    match args.next().as_deref() {
+        Some("dot") => system.write_dot(&mut writer),
+        Some("adjacency") => system.graph().write_adjacency_list(&mut writer),
+        _ => panic!("Export format should be one of dot or adjacency."),
+    }
+    .and_then(|_| writer.flush())
+    .expect("Failed to write the exported cave system.");
+}
+
+/// Prints each step of adding and reducing the two snailfish numbers given as arguments.
+fn trace_day_18(mut args: impl Iterator<Item = String>) {
+    let left = args.next().expect("Trace requires two snailfish numbers.");
+    let right = args.next().expect("Trace requires two snailfish numbers.");
+    trace_addition(&left, &right, |label, number| {
+        println!("{} => {}", label, number);
+    })
+    .expect("Failed to parse provided snailfish numbers.");
+}
+
+/// Writes the reconstructed day 19 map to stdout in the format given as an argument.
+fn export_day_19(mut args: impl Iterator<Item = String>) {
+    let map = reconstruct_map(&read_input(19), &ScannerSolverConfig::default());
+    let mut writer = stdout().lock();
+    match args.next().as_deref() {
+        Some("beacons") => map.write_beacons_csv(&mut writer),
+        Some("poses") => map.write_poses_csv(&mut writer),
+        Some("ply") => map.write_ply(&mut writer),
+        Some("obj") => map.write_obj(&mut writer),
+        Some("links") => map.write_link_graph(&mut writer),
+        _ => panic!("Export format should be one of beacons, poses, ply, obj or links."),
+    }
+    .and_then(|_| writer.flush())
+    .expect("Failed to write the exported map.");
+}
+
+/// Writes the day 20 image after each enhancement pass as a numbered sequence of PBM files in
+/// the directory given as an argument, optionally followed by the number of passes (default 50).
+fn export_day_20_frames(mut args: impl Iterator<Item = String>) {
+    let directory = args.next().expect("Frames requires an output directory.");
+    let passes = args.next().map_or(50, |passes| {
+        passes
+            .parse::<usize>()
+            .expect("Failed to parse the number of passes.")
+    });
+    enhancement_frames(&read_input(20))
+        .take(passes + 1)
+        .enumerate()
+        .for_each(|(pass, image)| {
+            let path = Path::new(&directory).join(format!("frame_{:03}.pbm", pass));
+            let mut writer = BufWriter::new(File::create(&path).expect("Failed to create frame."));
+            image
+                .write_pbm(&mut writer)
+                .and_then(|_| writer.flush())
+                .expect("Failed to write frame.");
+        });
+}